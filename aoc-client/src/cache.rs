@@ -0,0 +1,88 @@
+use std::{
+    fs, io,
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
+
+/// What the client fetches for each day
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Input,
+    Page,
+}
+
+impl Kind {
+    fn file_name(self) -> &'static str {
+        match self {
+            Kind::Input => "input.txt",
+            Kind::Page => "page.html",
+        }
+    }
+}
+
+/// A cached response, and when it was fetched
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub body: String,
+    pub fetched: SystemTime,
+}
+
+/// Responses from the site saved on disk, under `<year>/day<NN>/`.
+///
+/// Each entry sits next to a `.fetched` file holding the Unix time it was fetched at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The cache in `$AOC_CACHE_DIR`, falling back to `.aoc-cache` in the current directory,
+    /// which is the workspace root when the tools are run from there.
+    ///
+    /// CI should set `AOC_CACHE_DIR` to a directory it keeps between runs,
+    /// so that inputs aren't fetched again every time.
+    pub fn from_env() -> Self {
+        Self::new(dotenvy::var("AOC_CACHE_DIR").unwrap_or_else(|_| ".aoc-cache".to_owned()))
+    }
+
+    fn path(&self, year: &str, day: i32, kind: Kind) -> PathBuf {
+        self.dir
+            .join(year)
+            .join(format!("day{day:02}"))
+            .join(kind.file_name())
+    }
+
+    pub fn get(&self, year: &str, day: i32, kind: Kind) -> io::Result<Option<Entry>> {
+        let path = self.path(year, day, kind);
+        let body = match fs::read_to_string(&path) {
+            Ok(body) => body,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        // entries from before fetch times were recorded count as fetched at the epoch
+        let fetched = fs::read_to_string(path.with_extension("fetched"))
+            .ok()
+            .and_then(|secs| secs.trim().parse().ok())
+            .map_or(SystemTime::UNIX_EPOCH, |secs| {
+                SystemTime::UNIX_EPOCH + Duration::from_secs(secs)
+            });
+        Ok(Some(Entry { body, fetched }))
+    }
+
+    pub fn put(&self, year: &str, day: i32, kind: Kind, body: &str) -> io::Result<()> {
+        let path = self.path(year, day, kind);
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(&path, body)?;
+        let fetched = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default();
+        fs::write(
+            path.with_extension("fetched"),
+            format!("{}\n", fetched.as_secs()),
+        )
+    }
+}
@@ -0,0 +1,154 @@
+use std::{thread, time::Duration};
+
+use crate::{AocError, Cache, Kind, SubmitOutcome};
+
+/// A client for one year of Advent of Code.
+///
+/// Everything it fetches goes through its [`Cache`], and the input is served
+/// from there whenever it was fetched before.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AocClient {
+    /// Where the site lives, [`AocClient::BASE_URL`] unless testing against a stub
+    pub base_url: String,
+    pub year: String,
+    /// The `session` cookie, only needed for requests the cache can't answer
+    pub session: Option<String>,
+    pub user_agent: String,
+    pub cache: Cache,
+    /// Only ever read from the cache, failing with [`AocError::Offline`] on a miss
+    pub offline: bool,
+    /// How long to wait before retrying a server error, doubling after each retry.
+    /// Only fetches are retried, never a submission.
+    pub backoff: Duration,
+}
+
+impl AocClient {
+    pub const BASE_URL: &'static str = "https://adventofcode.com";
+    pub const USER_AGENT: &'static str = concat!("aoc-client/", env!("CARGO_PKG_VERSION"));
+    /// How many times a server error fetching a page is retried, waiting twice as long each time
+    pub const RETRIES: u32 = 3;
+    pub const BACKOFF: Duration = Duration::from_secs(1);
+
+    pub fn new(year: impl Into<String>) -> Self {
+        Self {
+            base_url: Self::BASE_URL.to_owned(),
+            year: year.into(),
+            session: None,
+            user_agent: Self::USER_AGENT.to_owned(),
+            cache: Cache::from_env(),
+            offline: false,
+            backoff: Self::BACKOFF,
+        }
+    }
+
+    /// Configures a client from the environment and `.env`.
+    ///
+    /// `AOC_YEAR` is required. `AOC_SESSION`, `AOC_USER_AGENT`, `AOC_BASE_URL`
+    /// and `AOC_CACHE_DIR` are optional, and a non-empty `AOC_OFFLINE` other than `0`
+    /// turns on offline mode.
+    pub fn from_env() -> Result<Self, AocError> {
+        let year = dotenvy::var("AOC_YEAR").map_err(|source| AocError::MissingConfig {
+            var: "AOC_YEAR",
+            source,
+        })?;
+        let mut client = Self::new(year);
+        client.session = dotenvy::var("AOC_SESSION").ok();
+        if let Ok(user_agent) = dotenvy::var("AOC_USER_AGENT") {
+            client.user_agent = user_agent;
+        }
+        if let Ok(base_url) = dotenvy::var("AOC_BASE_URL") {
+            client.base_url = base_url;
+        }
+        client.offline =
+            dotenvy::var("AOC_OFFLINE").is_ok_and(|offline| !offline.is_empty() && offline != "0");
+        Ok(client)
+    }
+
+    /// The puzzle page for a day, which the other URLs hang off
+    pub fn day_url(&self, day: i32) -> String {
+        let base_url = self.base_url.trim_end_matches('/');
+        format!("{base_url}/{}/day/{day}", self.year)
+    }
+
+    /// A day's input, from the cache if it was fetched before.
+    pub fn input(&self, day: i32) -> Result<String, AocError> {
+        if let Some(entry) = self.cache.get(&self.year, day, Kind::Input)? {
+            return Ok(entry.body);
+        }
+
+        let url = format!("{}/input", self.day_url(day));
+        if self.offline {
+            return Err(AocError::Offline { url });
+        }
+        let input = self.send("GET", &url, true, |request| Ok(request.call()?))?;
+        self.cache.put(&self.year, day, Kind::Input, &input)?;
+        Ok(input)
+    }
+
+    /// A day's puzzle page. The page gains part two once part one is solved,
+    /// so the cached copy is only used offline.
+    pub fn page(&self, day: i32) -> Result<String, AocError> {
+        let url = self.day_url(day);
+        if self.offline {
+            return match self.cache.get(&self.year, day, Kind::Page)? {
+                Some(entry) => Ok(entry.body),
+                None => Err(AocError::Offline { url }),
+            };
+        }
+
+        let page = self.send("GET", &url, true, |request| Ok(request.call()?))?;
+        self.cache.put(&self.year, day, Kind::Page, &page)?;
+        Ok(page)
+    }
+
+    /// Submits an answer to one part of a day.
+    ///
+    /// A server error isn't retried, as the site may have taken the answer anyway,
+    /// and answering again too soon locks the day for a while.
+    pub fn submit(&self, day: i32, level: u8, answer: &str) -> Result<SubmitOutcome, AocError> {
+        let url = format!("{}/answer", self.day_url(day));
+        if self.offline {
+            return Err(AocError::Offline { url });
+        }
+
+        let page = self.send("POST", &url, false, |request| {
+            Ok(request.send_form(&[("level", &level.to_string()), ("answer", answer)])?)
+        })?;
+
+        match SubmitOutcome::parse(&page) {
+            Some(outcome) => Ok(outcome),
+            None => Err(AocError::UnexpectedPage { url, page }),
+        }
+    }
+
+    /// Sends a request with the session cookie and reads the body of the response.
+    /// With `retry`, server errors are retried with backoff.
+    fn send(
+        &self,
+        method: &str,
+        url: &str,
+        retry: bool,
+        dispatch: impl Fn(ureq::Request) -> Result<ureq::Response, AocError>,
+    ) -> Result<String, AocError> {
+        let Some(session) = &self.session else {
+            return Err(AocError::MissingSession);
+        };
+        let mut delay = self.backoff;
+        let mut retries = 0;
+        loop {
+            let request = ureq::request(method, url)
+                .set("Cookie", &format!("session={session}"))
+                .set("User-Agent", &self.user_agent);
+            match dispatch(request) {
+                Ok(response) => return Ok(response.into_string()?),
+                Err(err) if retry && err.is_transient() && retries < Self::RETRIES => {
+                    eprintln!("{err}, retrying in {delay:?}");
+                    thread::sleep(delay);
+                    delay *= 2;
+                    retries += 1;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use std::{fmt, io};
+
+/// Anything that can go wrong talking to Advent of Code.
+#[derive(Debug)]
+pub enum AocError {
+    /// A variable wasn't set in the environment or in `.env`
+    MissingConfig {
+        var: &'static str,
+        source: dotenvy::Error,
+    },
+    /// `AOC_SESSION` isn't set, and the request can't be answered from the cache
+    MissingSession,
+    /// The site didn't accept `AOC_SESSION`, usually because the cookie expired
+    InvalidSession,
+    /// The puzzle at `url` hasn't unlocked yet
+    NotUnlocked { url: String },
+    /// Any other unsuccessful response, after retrying server errors where that's safe
+    Status { url: String, code: u16 },
+    /// `AOC_OFFLINE` is set, and `url` would have to be fetched
+    Offline { url: String },
+    /// A page that doesn't look like any the site is known to respond with
+    UnexpectedPage { url: String, page: String },
+    /// The request couldn't be sent, or a file couldn't be read or written
+    Io(io::Error),
+}
+
+impl AocError {
+    /// Classifies an unsuccessful response from `url`.
+    ///
+    /// The site answers a missing or expired session with a 400 asking to log in,
+    /// and puzzles that haven't unlocked yet with a 404.
+    pub fn from_status(url: &str, code: u16) -> Self {
+        match code {
+            400 => Self::InvalidSession,
+            404 => Self::NotUnlocked {
+                url: url.to_owned(),
+            },
+            code => Self::Status {
+                url: url.to_owned(),
+                code,
+            },
+        }
+    }
+
+    /// Whether the request is worth trying again
+    pub fn is_transient(&self) -> bool {
+        matches!(self, Self::Status { code, .. } if *code >= 500)
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingConfig { var, source } => {
+                write!(f, "{var} isn't set in the environment or in .env: {source}")
+            }
+            Self::MissingSession => write!(
+                f,
+                "AOC_SESSION isn't set in the environment or in .env. \
+                 Log in to Advent of Code and copy the session cookie into it"
+            ),
+            Self::InvalidSession => write!(
+                f,
+                "Advent of Code didn't accept the session, it has probably expired. \
+                 Log in again and copy the new session cookie into AOC_SESSION"
+            ),
+            Self::NotUnlocked { url } => write!(f, "{url} hasn't unlocked yet"),
+            Self::Status { url, code } => write!(f, "{url} responded with HTTP {code}"),
+            Self::Offline { url } => write!(f, "{url} isn't cached, and AOC_OFFLINE is set"),
+            Self::UnexpectedPage { url, .. } => {
+                write!(f, "could not make sense of the response from {url}")
+            }
+            Self::Io(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for AocError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::MissingConfig { source, .. } => Some(source),
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for AocError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<ureq::Error> for AocError {
+    fn from(err: ureq::Error) -> Self {
+        match err {
+            ureq::Error::Status(code, response) => Self::from_status(response.get_url(), code),
+            ureq::Error::Transport(transport) => Self::Io(io::Error::other(transport)),
+        }
+    }
+}
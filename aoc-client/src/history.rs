@@ -0,0 +1,183 @@
+use std::{fmt, fs, io, path::Path, time::Duration};
+
+use crate::SubmitOutcome;
+
+/// Every answer submitted for a challenge, as stored in the `guesses.txt` next to its `README.md`.
+///
+/// Each line holds the part, the outcome and the answer, separated by spaces.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct History {
+    pub guesses: Vec<Guess>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Guess {
+    pub level: u8,
+    pub answer: String,
+    pub outcome: SubmitOutcome,
+}
+
+/// Why an answer isn't worth submitting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    /// The part was already solved, with `answer`
+    Solved { answer: String },
+    /// The same answer was submitted before, and rejected
+    Rejected { outcome: SubmitOutcome },
+    /// The answer is at least one that was too high
+    TooHigh { bound: i128 },
+    /// The answer is at most one that was too low
+    TooLow { bound: i128 },
+}
+
+impl History {
+    pub fn parse(s: &str) -> Self {
+        let guesses = s
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.splitn(3, ' ');
+                let level = fields.next()?.parse().ok()?;
+                let outcome = outcome(fields.next()?)?;
+                let answer = fields.next()?.to_owned();
+                Some(Guess {
+                    level,
+                    answer,
+                    outcome,
+                })
+            })
+            .collect();
+        History { guesses }
+    }
+
+    /// Reads the `guesses.txt` in a challenge crate's directory, or starts an empty history.
+    pub fn load(dir: impl AsRef<Path>) -> io::Result<Self> {
+        match fs::read_to_string(dir.as_ref().join("guesses.txt")) {
+            Ok(s) => Ok(Self::parse(&s)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn save(&self, dir: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(dir.as_ref().join("guesses.txt"), self.to_string())
+    }
+
+    pub fn record(&mut self, level: u8, answer: &str, outcome: SubmitOutcome) {
+        self.guesses.push(Guess {
+            level,
+            answer: answer.to_owned(),
+            outcome,
+        });
+    }
+
+    /// Checks an answer to one part against the earlier guesses at it,
+    /// refusing it if the site's replies to those already rule it out.
+    ///
+    /// ```
+    /// use aoc_client::{History, Refusal, SubmitOutcome};
+    ///
+    /// let mut history = History::default();
+    /// history.record(1, "5000", SubmitOutcome::TooHigh);
+    /// history.record(1, "1200", SubmitOutcome::TooLow);
+    /// history.record(1, "3000", SubmitOutcome::Wrong);
+    ///
+    /// assert_eq!(history.check(1, "4000"), Ok(()));
+    /// assert_eq!(history.check(1, "6000"), Err(Refusal::TooHigh { bound: 5000 }));
+    /// assert_eq!(history.check(1, "1100"), Err(Refusal::TooLow { bound: 1200 }));
+    /// let outcome = SubmitOutcome::Wrong;
+    /// assert_eq!(history.check(1, "3000"), Err(Refusal::Rejected { outcome }));
+    /// assert_eq!(history.check(2, "3000"), Ok(()));
+    /// ```
+    pub fn check(&self, level: u8, answer: &str) -> Result<(), Refusal> {
+        let guesses = || self.guesses.iter().filter(move |g| g.level == level);
+
+        if let Some(guess) = guesses().find(|g| g.outcome == SubmitOutcome::Correct) {
+            return Err(Refusal::Solved {
+                answer: guess.answer.clone(),
+            });
+        }
+        let rejected = |g: &&Guess| {
+            matches!(
+                g.outcome,
+                SubmitOutcome::TooHigh | SubmitOutcome::TooLow | SubmitOutcome::Wrong
+            )
+        };
+        if let Some(guess) = guesses().filter(rejected).find(|g| g.answer == answer) {
+            return Err(Refusal::Rejected {
+                outcome: guess.outcome,
+            });
+        }
+
+        let Ok(answer) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+        let bound = |outcome| {
+            guesses()
+                .filter(move |g| g.outcome == outcome)
+                .filter_map(|g| g.answer.parse::<i128>().ok())
+        };
+        if let Some(bound) = bound(SubmitOutcome::TooHigh).min() {
+            if answer >= bound {
+                return Err(Refusal::TooHigh { bound });
+            }
+        }
+        if let Some(bound) = bound(SubmitOutcome::TooLow).max() {
+            if answer <= bound {
+                return Err(Refusal::TooLow { bound });
+            }
+        }
+        Ok(())
+    }
+}
+
+fn keyword(outcome: SubmitOutcome) -> &'static str {
+    match outcome {
+        SubmitOutcome::Correct => "correct",
+        SubmitOutcome::TooHigh => "too-high",
+        SubmitOutcome::TooLow => "too-low",
+        SubmitOutcome::Wrong => "wrong",
+        SubmitOutcome::RateLimited { .. } => "rate-limited",
+        SubmitOutcome::AlreadyCompleted => "already-completed",
+    }
+}
+
+fn outcome(keyword: &str) -> Option<SubmitOutcome> {
+    Some(match keyword {
+        "correct" => SubmitOutcome::Correct,
+        "too-high" => SubmitOutcome::TooHigh,
+        "too-low" => SubmitOutcome::TooLow,
+        "wrong" => SubmitOutcome::Wrong,
+        // the wait is long over by the time the history is read back
+        "rate-limited" => SubmitOutcome::RateLimited {
+            wait: Duration::ZERO,
+        },
+        "already-completed" => SubmitOutcome::AlreadyCompleted,
+        _ => return None,
+    })
+}
+
+impl fmt::Display for History {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for guess in &self.guesses {
+            writeln!(
+                f,
+                "{} {} {}",
+                guess.level,
+                keyword(guess.outcome),
+                guess.answer
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Refusal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Solved { answer } => write!(f, "already solved with {answer}"),
+            Self::Rejected { outcome } => write!(f, "already rejected: {outcome}"),
+            Self::TooHigh { bound } => write!(f, "{bound} was already too high"),
+            Self::TooLow { bound } => write!(f, "{bound} was already too low"),
+        }
+    }
+}
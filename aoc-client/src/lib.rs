@@ -1,81 +1,117 @@
-use std::{fmt::Display, path::Path, time::Instant};
+use std::{path::Path, time::Instant};
 
-use aoc::Parser;
-use scraper::{Html, Selector};
+use aoc::{IntoAnswer, Parser};
 
-pub fn base_url_for_day(day: i32) -> String {
-    let year = dotenvy::var("AOC_YEAR").unwrap();
-    format!("https://adventofcode.com/{year}/day/{day}")
-}
+mod cache;
+mod client;
+mod error;
+mod history;
+pub mod stub;
+mod submit;
+
+pub use cache::{Cache, Entry, Kind};
+pub use client::AocClient;
+pub use error::AocError;
+pub use history::{Guess, History, Refusal};
+pub use submit::SubmitOutcome;
 
-pub fn run_and_upload<'a, C: Parser<'a>>(name: &str, input: &'static str) {
+/// Solves the part of the challenge that's next to be solved, and submits the answer,
+/// returning what the site made of it if it was submitted.
+pub fn run_and_upload<'a, C: Parser<'a>>(name: &str, input: &'static str) -> Option<SubmitOutcome> {
     println!("\nRunning challenge {}", name);
 
     let start = Instant::now();
-    let challenge = C::must_parse(input);
+    let challenge = match C::try_parse(input) {
+        Ok(challenge) => challenge,
+        Err(err) => {
+            eprintln!("{err}");
+            return None;
+        }
+    };
 
-    let file = Path::new("challenges").join(name).join("README.md");
-    let readme = std::fs::read_to_string(file).expect("could not read file");
+    let dir = Path::new("challenges").join(name);
+    let file = dir.join("README.md");
+    let readme = match std::fs::read_to_string(&file) {
+        Ok(readme) => readme,
+        Err(err) => {
+            eprintln!("could not read {}: {err}", file.display());
+            return None;
+        }
+    };
     let part_one = !readme.contains("--- Part Two ---");
 
-    if part_one {
-        let p1 = challenge.part_one();
+    let (level, answer) = if part_one {
+        let p1 = challenge.part_one().into_answer();
         println!("took: {:?}", start.elapsed());
         println!("\tAnswer to part one: {p1}. ({:?})", start.elapsed());
-        submit(name, 1, p1);
+        (1, p1)
     } else {
-        let p2 = challenge.part_two();
+        let p2 = challenge.part_two().into_answer();
         println!("\tAnswer to part two: {p2}. ({:?})", start.elapsed());
-        submit(name, 2, p2);
-    }
-}
+        (2, p2)
+    };
 
-fn submit(name: &str, level: usize, answer: impl Display) {
-    let session = dotenvy::var("AOC_SESSION").unwrap();
-    let day = name[3..].parse::<i32>().unwrap();
-    let url = format!("{}/answer", base_url_for_day(day));
-
-    let resp = ureq::post(&url)
-        .set("Cookie", &format!("session={session}"))
-        .send_form(&[
-            ("level", &format!("{level}")),
-            ("answer", &format!("{answer}")),
-        ])
-        .unwrap()
-        .into_string()
-        .unwrap();
-
-    let html = Html::parse_document(&resp);
-    let selector = Selector::parse("article span.day-success").unwrap();
-    if html.select(&selector).count() > 0 {
-        println!("Correct!");
-    } else {
-        println!("Wrong!");
-        let file = Path::new("challenges").join(name).join("resp.html");
-        std::fs::write(file, resp).unwrap();
+    // a real answer can sit next to a type boundary too, so this can be overridden
+    if answer.looks_overflowed() {
+        if dotenvy::var("AOC_SUBMIT_OVERFLOWED").is_ok_and(|allow| allow == "1") {
+            println!("Warning: {answer} looks like it overflowed, submitting it anyway");
+        } else {
+            println!(
+                "Not submitting, {answer} looks like it overflowed. \
+                 Set AOC_SUBMIT_OVERFLOWED=1 to submit it anyway"
+            );
+            return None;
+        }
     }
-}
-
-pub fn get_input(day: i32) -> String {
-    let session = dotenvy::var("AOC_SESSION").unwrap();
-    let url = format!("{}/input", base_url_for_day(day));
-
-    ureq::get(&url)
-        .set("Cookie", &format!("session={session}"))
-        .send_bytes(&[])
-        .unwrap()
-        .into_string()
-        .unwrap()
-}
+    let Some(answer) = answer.canonical() else {
+        println!("Not submitting, read the answer and submit it by hand");
+        return None;
+    };
 
-pub fn get_page_html(day: i32) -> String {
-    let session = dotenvy::var("AOC_SESSION").unwrap();
-    let url = base_url_for_day(day);
+    // don't spend a submission, and risk a lockout, on an answer that's known to be wrong
+    let mut history = match History::load(&dir) {
+        Ok(history) => history,
+        Err(err) => {
+            eprintln!("could not read the guesses in {}: {err}", dir.display());
+            return None;
+        }
+    };
+    if let Err(refusal) = history.check(level, &answer) {
+        println!("Not submitting, {refusal}");
+        return None;
+    }
 
-    ureq::get(&url)
-        .set("Cookie", &format!("session={session}"))
-        .send_bytes(&[])
-        .unwrap()
-        .into_string()
-        .unwrap()
+    let Some(day) = name
+        .strip_prefix("day")
+        .and_then(|day| day.parse::<i32>().ok())
+    else {
+        eprintln!("could not tell which day {name} is, expected a name like day07");
+        return None;
+    };
+    let submitted = AocClient::from_env().and_then(|client| client.submit(day, level, &answer));
+    match submitted {
+        Ok(outcome) => {
+            println!("{outcome}");
+            history.record(level, &answer, outcome);
+            if let Err(err) = history.save(&dir) {
+                eprintln!("could not save the guesses in {}: {err}", dir.display());
+            }
+            Some(outcome)
+        }
+        Err(AocError::UnexpectedPage { url, page }) => {
+            let file = dir.join("resp.html");
+            eprintln!(
+                "could not make sense of the response from {url}, see {}",
+                file.display()
+            );
+            if let Err(err) = std::fs::write(&file, page) {
+                eprintln!("could not write {}: {err}", file.display());
+            }
+            None
+        }
+        Err(err) => {
+            eprintln!("error: {err}");
+            None
+        }
+    }
 }
@@ -0,0 +1,99 @@
+use std::{fmt, time::Duration};
+
+use scraper::{Html, Selector};
+
+/// What Advent of Code made of a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubmitOutcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint as to which way
+    Wrong,
+    /// Another answer was submitted too recently, and none can be until `wait` is up
+    RateLimited {
+        wait: Duration,
+    },
+    /// The part was already solved, or part one hasn't been yet
+    AlreadyCompleted,
+}
+
+impl SubmitOutcome {
+    /// Reads the outcome from the page the site responds to a submission with,
+    /// or `None` if the page doesn't look like any of them.
+    ///
+    /// ```
+    /// use aoc_client::SubmitOutcome;
+    ///
+    /// let page = "<main><article><p>That's not the right answer; \
+    ///             your answer is too low.  Please wait one minute before trying again.\
+    ///             </p></article></main>";
+    /// assert_eq!(SubmitOutcome::parse(page), Some(SubmitOutcome::TooLow));
+    ///
+    /// let page = "<main><article><p>You gave an answer too recently; you have to wait \
+    ///             after submitting an answer before trying again.  You have 1m 13s left \
+    ///             to wait.</p></article></main>";
+    /// let wait = std::time::Duration::from_secs(73);
+    /// assert_eq!(SubmitOutcome::parse(page), Some(SubmitOutcome::RateLimited { wait }));
+    /// ```
+    pub fn parse(html: &str) -> Option<Self> {
+        let html = Html::parse_document(html);
+        let article = Selector::parse("article").unwrap();
+        let text = html.select(&article).next()?.text().collect::<String>();
+        let text = text.trim_start();
+
+        let outcome = if text.starts_with("That's the right answer") {
+            Self::Correct
+        } else if text.starts_with("That's not the right answer") {
+            if text.contains("your answer is too high") {
+                Self::TooHigh
+            } else if text.contains("your answer is too low") {
+                Self::TooLow
+            } else {
+                Self::Wrong
+            }
+        } else if text.starts_with("You gave an answer too recently") {
+            Self::RateLimited {
+                wait: wait(text).unwrap_or(Duration::from_secs(60)),
+            }
+        } else if text.starts_with("You don't seem to be solving the right level") {
+            Self::AlreadyCompleted
+        } else {
+            return None;
+        };
+        Some(outcome)
+    }
+}
+
+/// The wait in "You have 1m 13s left to wait."
+fn wait(text: &str) -> Option<Duration> {
+    let (_, rest) = text.split_once("You have ")?;
+    let (wait, _) = rest.split_once(" left to wait")?;
+    let mut secs = 0;
+    for part in wait.split_whitespace() {
+        let (n, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+        let n: u64 = n.parse().ok()?;
+        secs += match unit {
+            "h" => n * 3600,
+            "m" => n * 60,
+            "s" => n,
+            _ => return None,
+        };
+    }
+    Some(Duration::from_secs(secs))
+}
+
+impl fmt::Display for SubmitOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Correct => write!(f, "Correct!"),
+            Self::TooHigh => write!(f, "Wrong, too high"),
+            Self::TooLow => write!(f, "Wrong, too low"),
+            Self::Wrong => write!(f, "Wrong!"),
+            Self::RateLimited { wait } => {
+                write!(f, "Answered too recently, wait {}s", wait.as_secs())
+            }
+            Self::AlreadyCompleted => write!(f, "Already completed"),
+        }
+    }
+}
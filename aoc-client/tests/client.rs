@@ -0,0 +1,197 @@
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use aoc_client::{
+    stub::{Response, StubServer},
+    AocClient, AocError, Cache, Kind, SubmitOutcome,
+};
+use tempfile::TempDir;
+
+const INPUT: &str = include_str!("fixtures/input.txt");
+const PAGE: &str = include_str!("fixtures/day01.html");
+
+/// A client for 2023 pointed at the stub, that retries straight away,
+/// with its own empty cache that lasts as long as the `TempDir`
+fn client(server: &StubServer) -> (AocClient, TempDir) {
+    let cache = tempfile::tempdir().unwrap();
+
+    let mut client = AocClient::new("2023");
+    client.base_url = server.url();
+    client.session = Some("53616c7465645f5f".to_owned());
+    client.user_agent = "aoc-client tests".to_owned();
+    client.cache = Cache::new(cache.path());
+    client.backoff = Duration::ZERO;
+    (client, cache)
+}
+
+fn site(request: &aoc_client::stub::Request) -> Response {
+    match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/2023/day/1") => Response::ok(PAGE),
+        ("GET", "/2023/day/1/input") => Response::ok(INPUT),
+        ("GET", "/2023/day/25/input") => Response::status(404),
+        _ => Response::status(400),
+    }
+}
+
+#[test]
+fn input() {
+    let server = StubServer::start(site);
+    let (client, _cache) = client(&server);
+
+    assert_eq!(client.input(1).unwrap(), INPUT);
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].path, "/2023/day/1/input");
+    assert_eq!(
+        requests[0].header("cookie"),
+        Some("session=53616c7465645f5f")
+    );
+    assert_eq!(requests[0].header("user-agent"), Some("aoc-client tests"));
+
+    // the second time round comes from the cache
+    assert_eq!(client.input(1).unwrap(), INPUT);
+    assert_eq!(server.requests().len(), 1);
+    let entry = client.cache.get("2023", 1, Kind::Input).unwrap().unwrap();
+    assert_eq!(entry.body, INPUT);
+    assert!(entry.fetched.elapsed().unwrap() < Duration::from_secs(60));
+}
+
+#[test]
+fn offline() {
+    let server = StubServer::start(site);
+    let (mut client, _cache) = client(&server);
+    client.offline = true;
+    client.session = None;
+
+    assert!(matches!(client.input(1), Err(AocError::Offline { .. })));
+    assert!(matches!(client.page(1), Err(AocError::Offline { .. })));
+    assert!(matches!(
+        client.submit(1, 1, "142"),
+        Err(AocError::Offline { .. })
+    ));
+
+    client.cache.put("2023", 1, Kind::Input, INPUT).unwrap();
+    client.cache.put("2023", 1, Kind::Page, PAGE).unwrap();
+    assert_eq!(client.input(1).unwrap(), INPUT);
+    assert_eq!(client.page(1).unwrap(), PAGE);
+    assert!(server.requests().is_empty());
+}
+
+#[test]
+fn page() {
+    let server = StubServer::start(site);
+    let (client, _cache) = client(&server);
+
+    assert_eq!(client.page(1).unwrap(), PAGE);
+    // online, the page is always fetched again in case part two has unlocked
+    assert_eq!(client.page(1).unwrap(), PAGE);
+    assert_eq!(server.requests().len(), 2);
+    let entry = client.cache.get("2023", 1, Kind::Page).unwrap().unwrap();
+    assert_eq!(entry.body, PAGE);
+}
+
+#[test]
+fn errors() {
+    let server = StubServer::start(site);
+    let (mut client, _cache) = client(&server);
+
+    assert!(matches!(
+        client.input(25),
+        Err(AocError::NotUnlocked { url }) if url.ends_with("/2023/day/25/input")
+    ));
+    assert!(matches!(client.input(2), Err(AocError::InvalidSession)));
+
+    client.session = None;
+    assert!(matches!(client.input(3), Err(AocError::MissingSession)));
+}
+
+#[test]
+fn retries_server_errors() {
+    let failures = AtomicUsize::new(0);
+    let server = StubServer::start(move |request| {
+        if failures.fetch_add(1, Ordering::Relaxed) < 1 {
+            Response::status(503)
+        } else {
+            site(request)
+        }
+    });
+    let (client, _cache) = client(&server);
+
+    assert_eq!(client.input(1).unwrap(), INPUT);
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[test]
+fn gives_up_on_server_errors() {
+    let server = StubServer::start(|_| Response::status(502));
+    let (client, _cache) = client(&server);
+
+    assert!(matches!(
+        client.input(1),
+        Err(AocError::Status { code: 502, .. })
+    ));
+    // the first try, then each retry
+    assert_eq!(server.requests().len(), AocClient::RETRIES as usize + 1);
+}
+
+#[test]
+fn submit_is_not_retried() {
+    let server = StubServer::start(|_| Response::status(502));
+    let (client, _cache) = client(&server);
+
+    assert!(matches!(
+        client.submit(1, 1, "142"),
+        Err(AocError::Status { code: 502, .. })
+    ));
+    // the answer may have been taken, so sending it again could lock the day
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[test]
+fn submit() {
+    let outcomes = [
+        (
+            include_str!("fixtures/answer-correct.html"),
+            SubmitOutcome::Correct,
+        ),
+        (
+            include_str!("fixtures/answer-too-high.html"),
+            SubmitOutcome::TooHigh,
+        ),
+        (
+            include_str!("fixtures/answer-too-low.html"),
+            SubmitOutcome::TooLow,
+        ),
+        (
+            include_str!("fixtures/answer-too-recent.html"),
+            SubmitOutcome::RateLimited {
+                wait: Duration::from_secs(73),
+            },
+        ),
+        (
+            include_str!("fixtures/answer-wrong-level.html"),
+            SubmitOutcome::AlreadyCompleted,
+        ),
+    ];
+    for (page, outcome) in outcomes {
+        let server = StubServer::start(move |request| match request.path.as_str() {
+            "/2023/day/1/answer" if request.method == "POST" => Response::ok(page),
+            _ => Response::status(404),
+        });
+        let (client, _cache) = client(&server);
+
+        assert_eq!(client.submit(1, 2, "142").unwrap(), outcome);
+        let requests = server.requests();
+        assert_eq!(requests[0].form("level"), Some("2"));
+        assert_eq!(requests[0].form("answer"), Some("142"));
+    }
+
+    let server = StubServer::start(|_| Response::ok(PAGE));
+    let (client, _cache) = client(&server);
+    assert!(matches!(
+        client.submit(1, 1, "142"),
+        Err(AocError::UnexpectedPage { page, .. }) if page == PAGE
+    ));
+}
@@ -0,0 +1,82 @@
+//! A global allocator that keeps count, installed by the `count-allocs` feature.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering::Relaxed},
+};
+
+use crate::AllocStats;
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+static COUNT: AtomicUsize = AtomicUsize::new(0);
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+struct Counting;
+
+fn grow(size: usize) {
+    let current = CURRENT.fetch_add(size, Relaxed) + size;
+    PEAK.fetch_max(current, Relaxed);
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            COUNT.fetch_add(1, Relaxed);
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            COUNT.fetch_add(1, Relaxed);
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT.fetch_sub(layout.size(), Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            COUNT.fetch_add(1, Relaxed);
+            CURRENT.fetch_sub(layout.size(), Relaxed);
+            grow(new_size);
+        }
+        new
+    }
+}
+
+/// The allocator's counters at the start of a phase
+pub(crate) struct Snapshot {
+    count: usize,
+    current: usize,
+}
+
+impl Snapshot {
+    /// Starts a phase, resetting the peak to what is live right now.
+    pub(crate) fn take() -> Self {
+        let current = CURRENT.load(Relaxed);
+        PEAK.store(current, Relaxed);
+        Snapshot {
+            count: COUNT.load(Relaxed),
+            current,
+        }
+    }
+
+    pub(crate) fn stats(self) -> AllocStats {
+        AllocStats {
+            count: COUNT.load(Relaxed) - self.count,
+            peak_bytes: PEAK.load(Relaxed).saturating_sub(self.current),
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use std::fmt;
+
+/// The answer to one part of a challenge.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Int(i128),
+    Str(String),
+    /// Multi-line ASCII art, where the answer is the letters it spells out
+    Art(String),
+}
+
+impl Answer {
+    /// The exact form to submit, if there is one.
+    /// ASCII art has to be read by a human first.
+    ///
+    /// ```
+    /// use aoc::Answer;
+    /// assert_eq!(Answer::Int(-12).canonical().as_deref(), Some("-12"));
+    /// assert_eq!(Answer::Str("ABC".into()).canonical().as_deref(), Some("ABC"));
+    /// assert_eq!(Answer::Art("#..#\n####\n".into()).canonical(), None);
+    /// ```
+    pub fn canonical(&self) -> Option<String> {
+        match self {
+            Answer::Int(n) => Some(n.to_string()),
+            Answer::Str(s) => Some(s.trim().to_owned()),
+            Answer::Art(_) => None,
+        }
+    }
+
+    /// Whether the value sits right next to an integer type boundary,
+    /// which is usually a sign of wrapping arithmetic rather than a real answer.
+    ///
+    /// ```
+    /// use aoc::Answer;
+    /// assert!(Answer::Int((-3i32) as u32 as i128).looks_overflowed());
+    /// assert!(Answer::Int(i64::MAX as i128).looks_overflowed());
+    /// assert!(!Answer::Int(54331).looks_overflowed());
+    /// ```
+    pub fn looks_overflowed(&self) -> bool {
+        let Answer::Int(n) = self else {
+            return false;
+        };
+        let n = n.unsigned_abs();
+        [31, 32, 63, 64]
+            .into_iter()
+            .any(|bits| n.abs_diff(1 << bits) <= 1 << (bits / 2))
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(n) => n.fmt(f),
+            Answer::Str(s) => s.fmt(f),
+            Answer::Art(art) => write!(f, "\n{art}"),
+        }
+    }
+}
+
+/// A value that a challenge part can return.
+pub trait IntoAnswer: fmt::Display {
+    fn into_answer(self) -> Answer;
+}
+
+impl IntoAnswer for Answer {
+    fn into_answer(self) -> Answer {
+        self
+    }
+}
+
+macro_rules! int_answer {
+    ($($t:ty),*) => {$(
+        impl IntoAnswer for $t {
+            fn into_answer(self) -> Answer {
+                Answer::Int(self as i128)
+            }
+        }
+    )*};
+}
+int_answer!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, i128);
+
+impl IntoAnswer for u128 {
+    fn into_answer(self) -> Answer {
+        match i128::try_from(self) {
+            Ok(n) => Answer::Int(n),
+            Err(_) => Answer::Str(self.to_string()),
+        }
+    }
+}
+
+impl IntoAnswer for String {
+    fn into_answer(self) -> Answer {
+        Answer::Str(self)
+    }
+}
+
+impl IntoAnswer for &str {
+    fn into_answer(self) -> Answer {
+        Answer::Str(self.to_owned())
+    }
+}
@@ -0,0 +1,103 @@
+/// Generates the divan benches for a challenge crate, along with their `main`.
+///
+/// Every day gets the same set, run against the crate's `input.txt`:
+/// `only_parse`, `only_part_one` and `only_part_two` time one phase on its own,
+/// `part_one` and `part_two` include the parse, and `complete` is [`run`](crate::run),
+/// the same as the `all` runner times. For a solution that
+/// [reparses](crate::Challenge::REPARSES), `complete` parses twice, which `main` points out.
+/// The crate needs `divan` as a dev-dependency, and a `[[bench]]` with `harness = false`.
+///
+/// A [`ChallengeRef`](crate::ChallengeRef) solution is marked with `ref`, so that
+/// `only_part_one` and `only_part_two` borrow a single parse rather than parsing
+/// a fresh copy for every iteration.
+///
+/// The divan options default to `sample_count = 100, sample_size = 1000`.
+/// Slow days can lower them, and those where part two dominates can lower them
+/// for just the benches that run part two.
+///
+/// ```ignore
+/// aoc::bench_main!(day01::Solution<'static>);
+/// aoc::bench_main!(day17::Solution, sample_count = 10, sample_size = 10);
+/// aoc::bench_main!(ref day19::Solution);
+/// aoc::bench_main!(
+///     day14::Solution,
+///     sample_count = 100, sample_size = 1000;
+///     part_two: sample_count = 20, sample_size = 20,
+/// );
+/// ```
+#[macro_export]
+macro_rules! bench_main {
+    // one part on its own, consuming a fresh parse each time or borrowing a single one
+    (@value $bencher:ident, $solution:ty, $part:ident) => {
+        $bencher
+            .with_inputs(|| <$solution as $crate::Parser>::must_parse(INPUT))
+            .bench_values(<$solution as $crate::Challenge>::$part)
+    };
+    (@by_ref $bencher:ident, $solution:ty, $part:ident) => {{
+        let challenge = <$solution as $crate::Parser>::must_parse(INPUT);
+        $bencher.bench(|| <$solution as $crate::ChallengeRef>::$part(&challenge))
+    }};
+    (@$kind:ident $solution:ty $(,)?) => {
+        $crate::bench_main!(@$kind $solution, sample_count = 100, sample_size = 1000);
+    };
+    (@$kind:ident $solution:ty, $($key:ident = $value:literal),+ $(,)?) => {
+        $crate::bench_main!(@$kind $solution, $($key = $value),+; part_two: $($key = $value),+);
+    };
+    (
+        @$kind:ident $solution:ty, $($key:ident = $value:literal),+;
+        part_two: $($key_two:ident = $value_two:literal),+ $(,)?
+    ) => {
+        const INPUT: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt"));
+
+        fn main() {
+            if <$solution as $crate::Challenge>::REPARSES {
+                eprintln!("note: `complete` parses the input again for part two");
+            }
+            ::divan::main();
+        }
+
+        #[::divan::bench($($key = $value),+)]
+        fn only_parse(bencher: ::divan::Bencher) {
+            bencher
+                .counter(::divan::counter::BytesCount::new(INPUT.len()))
+                .bench(|| <$solution as $crate::Parser>::parse(::divan::black_box(INPUT)))
+        }
+
+        #[::divan::bench($($key = $value),+)]
+        fn only_part_one(bencher: ::divan::Bencher) {
+            $crate::bench_main!(@$kind bencher, $solution, part_one)
+        }
+
+        #[::divan::bench($($key_two = $value_two),+)]
+        fn only_part_two(bencher: ::divan::Bencher) {
+            $crate::bench_main!(@$kind bencher, $solution, part_two)
+        }
+
+        #[::divan::bench($($key = $value),+)]
+        fn part_one(bencher: ::divan::Bencher) {
+            bencher
+                .counter(::divan::counter::BytesCount::new(INPUT.len()))
+                .bench(|| $crate::run_part_one::<$solution>(::divan::black_box(INPUT)))
+        }
+
+        #[::divan::bench($($key_two = $value_two),+)]
+        fn part_two(bencher: ::divan::Bencher) {
+            bencher
+                .counter(::divan::counter::BytesCount::new(INPUT.len()))
+                .bench(|| $crate::run_part_two::<$solution>(::divan::black_box(INPUT)))
+        }
+
+        #[::divan::bench($($key_two = $value_two),+)]
+        fn complete(bencher: ::divan::Bencher) {
+            bencher
+                .counter(::divan::counter::BytesCount::new(INPUT.len()))
+                .bench(|| $crate::run::<$solution>(::divan::black_box(INPUT)))
+        }
+    };
+    (ref $($args:tt)+) => {
+        $crate::bench_main!(@by_ref $($args)+);
+    };
+    ($solution:ty $(, $($args:tt)+)?) => {
+        $crate::bench_main!(@value $solution $(, $($args)+)?);
+    };
+}
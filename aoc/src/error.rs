@@ -0,0 +1,108 @@
+use std::fmt;
+
+use nom::error::ErrorKind;
+
+/// A parse failure, located within the original input.
+///
+/// ```
+/// let err = aoc::ParseError::new("1 2\n3 x 4\n", 6, "Digit");
+/// assert_eq!((err.line, err.column), (2, 3));
+/// assert_eq!(err.to_string(), "\
+/// error: expected Digit at line 2, column 3
+///   |
+/// 2 | 3 x 4
+///   |   ^
+/// ");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Byte offset into the input
+    pub offset: usize,
+    /// 1-indexed line number
+    pub line: usize,
+    /// 1-indexed column, in characters
+    pub column: usize,
+    /// The text of the offending line, without the line ending
+    pub text: String,
+    /// The construct the parser expected to find
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(input: &str, offset: usize, expected: impl Into<String>) -> Self {
+        let offset = offset.min(input.len());
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+
+        Self {
+            offset,
+            line: input[..line_start].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            text: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_owned(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Converts the error returned by a nom parser run over `input`.
+    pub fn from_nom(input: &str, err: nom::Err<nom::error::Error<&str>>) -> Self {
+        match err {
+            nom::Err::Error(e) | nom::Err::Failure(e) => {
+                Self::new(input, offset_in(input, e.input), expected(&e.code))
+            }
+            nom::Err::Incomplete(_) => Self::new(input, input.len(), "more input"),
+        }
+    }
+}
+
+/// What an error of `kind` expected to find. nom's descriptions are names more than phrases,
+/// eg "Needed data size is too large", so those of the kinds that the hand-rolled parsers
+/// return through [`safe`](crate::safe) are reworded.
+fn expected(kind: &ErrorKind) -> &str {
+    match kind {
+        ErrorKind::Eof => "more input",
+        ErrorKind::CrLf => "a line ending",
+        ErrorKind::Space => "a space",
+        ErrorKind::Digit => "a digit",
+        ErrorKind::Alpha => "a letter",
+        ErrorKind::Char => "a valid character",
+        ErrorKind::OneOf => "one of the characters allowed here",
+        ErrorKind::TooLarge => "a smaller number",
+        ErrorKind::Count => "fewer of these",
+        ErrorKind::Many1 => "at least one item",
+        ErrorKind::LengthValue => "a line of the usual length",
+        ErrorKind::Verify => "input the puzzle allows here",
+        kind => kind.description(),
+    }
+}
+
+/// Where `rest` starts within `input`. nom hands back subslices of the original input,
+/// but fall back to comparing lengths in case a parser built its own.
+fn offset_in(input: &str, rest: &str) -> usize {
+    let start = input.as_ptr() as usize;
+    let pos = rest.as_ptr() as usize;
+    if (start..=start + input.len()).contains(&pos) {
+        pos - start
+    } else {
+        input.len().saturating_sub(rest.len())
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let gutter = " ".repeat(self.line.to_string().len());
+        writeln!(
+            f,
+            "error: expected {} at line {}, column {}",
+            self.expected, self.line, self.column
+        )?;
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{} | {}", self.line, self.text)?;
+        writeln!(f, "{gutter} | {:>width$}", "^", width = self.column)
+    }
+}
+
+impl std::error::Error for ParseError {}
@@ -0,0 +1,215 @@
+use crate::{IntoAnswer, Parser, Phase};
+
+/// An example input from a challenge's README, along with the answers the text gives for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example<'a> {
+    pub input: &'a str,
+    pub part_one: Option<&'a str>,
+    pub part_two: Option<&'a str>,
+}
+
+impl<'a> Example<'a> {
+    pub fn answer(&self, phase: Phase) -> Option<&'a str> {
+        match phase {
+            Phase::PartOne => self.part_one,
+            Phase::PartTwo => self.part_two,
+            Phase::Parse | Phase::Both => None,
+        }
+    }
+}
+
+/// Splits the README at the part two heading, if it has one
+fn split_parts(readme: &str) -> (&str, Option<&str>) {
+    let mut offset = 0;
+    for line in readme.split_inclusive('\n') {
+        if line.starts_with("## ") && line.contains("Part Two") {
+            return (&readme[..offset], Some(&readme[offset..]));
+        }
+        offset += line.len();
+    }
+    (readme, None)
+}
+
+/// The fenced code blocks in some markdown, as their info strings and contents
+fn blocks(text: &str) -> Vec<(&str, &str)> {
+    let mut blocks = Vec::new();
+    let mut open = None;
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+
+        let line = line.trim_end();
+        match open {
+            None => {
+                if let Some(info) = line.strip_prefix("```") {
+                    open = Some((info.trim(), offset));
+                }
+            }
+            Some((info, content_start)) if line == "```" => {
+                blocks.push((info, &text[content_start..start]));
+                open = None;
+            }
+            Some(_) => {}
+        }
+    }
+    blocks
+}
+
+/// The last emphasised code span in the text, eg **`142`**
+fn last_answer(text: &str) -> Option<&str> {
+    let (_, rest) = text.rsplit_once("**`")?;
+    let (answer, _) = rest.split_once("`**")?;
+    Some(answer)
+}
+
+/// Finds the examples in a challenge's README.
+///
+/// A code block with an `example` info string is taken as annotated,
+/// eg ```` ```example part_one=142 part_two=281 ````.
+/// If no block is annotated with a part one answer, the first block is used,
+/// with the last emphasised answer before the part two heading.
+/// Part two falls back to the part one example, with the last emphasised answer after the heading.
+///
+/// ```
+/// let readme = concat!(
+///     "## --- Day 1: Sample ---\n",
+///     "```\n1 2\n```\n",
+///     "This adds up to **`3`**.\n",
+///     "## --- Part Two ---\n",
+///     "```example part_two=6\n4 2\n```\n",
+///     "Multiplied by three, this is **`18`**.\n",
+/// );
+/// let examples = aoc::examples(readme);
+/// assert_eq!(examples.len(), 2);
+/// assert_eq!((examples[0].input, examples[0].part_two), ("4 2\n", Some("6")));
+/// assert_eq!((examples[1].input, examples[1].part_one), ("1 2\n", Some("3")));
+/// ```
+pub fn examples(readme: &str) -> Vec<Example<'_>> {
+    let (part_one_text, part_two_text) = split_parts(readme);
+    let part_one_blocks = blocks(part_one_text);
+    let part_two_blocks = part_two_text.map(blocks).unwrap_or_default();
+
+    let mut examples: Vec<_> = part_one_blocks
+        .iter()
+        .chain(&part_two_blocks)
+        .filter_map(|&(info, input)| {
+            let annotations = info.strip_prefix("example")?;
+            let mut example = Example {
+                input,
+                part_one: None,
+                part_two: None,
+            };
+            for annotation in annotations.split_whitespace() {
+                match annotation.split_once('=') {
+                    Some(("part_one", answer)) => example.part_one = Some(answer),
+                    Some(("part_two", answer)) => example.part_two = Some(answer),
+                    _ => panic!("unknown example annotation {annotation:?}"),
+                }
+            }
+            Some(example)
+        })
+        .collect();
+
+    let annotated_one = examples
+        .iter()
+        .find(|e| e.part_one.is_some())
+        .map(|e| e.input);
+    let annotated_two = examples.iter().any(|e| e.part_two.is_some());
+    let part_one = annotated_one.or_else(|| part_one_blocks.first().map(|&(_, input)| input));
+
+    let mut fallback = Example {
+        input: part_one.unwrap_or_default(),
+        part_one: None,
+        part_two: None,
+    };
+    if annotated_one.is_none() {
+        fallback.part_one = part_one.and(last_answer(part_one_text));
+    }
+    if !annotated_two {
+        fallback.part_two = part_one.and(part_two_text.and_then(last_answer));
+    }
+    if fallback.part_one.is_some() || fallback.part_two.is_some() {
+        examples.push(fallback);
+    }
+    examples
+}
+
+#[doc(hidden)]
+pub fn check_parse<C: Parser<'static>>(readme: &'static str) {
+    let examples = examples(readme);
+    assert!(!examples.is_empty(), "README has no examples");
+    for example in examples {
+        if let Err(err) = C::try_parse(example.input) {
+            panic!("could not parse example\n{}\n{err}", example.input);
+        }
+    }
+}
+
+#[doc(hidden)]
+pub fn check_part<C: Parser<'static>>(readme: &'static str, part: &str) {
+    let phase = match part {
+        "part_one" => Phase::PartOne,
+        "part_two" => Phase::PartTwo,
+        _ => panic!("expected part_one or part_two, found {part}"),
+    };
+
+    let mut checked = 0;
+    for example in examples(readme) {
+        let Some(expected) = example.answer(phase) else {
+            continue;
+        };
+        let challenge = C::must_parse(example.input);
+        let answer = match phase {
+            Phase::PartOne => challenge.part_one().into_answer(),
+            _ => challenge.part_two().into_answer(),
+        };
+        assert_eq!(
+            answer.canonical().as_deref(),
+            Some(expected),
+            "{phase} of example\n{}",
+            example.input
+        );
+        checked += 1;
+    }
+    assert!(
+        checked > 0,
+        "README has no example answer for {phase}, annotate a block with ```example {part}=..."
+    );
+}
+
+/// Generates tests from the examples in the crate's `README.md`, as found by [`examples`].
+///
+/// Leave out a part whose examples can't be run as-is, eg when part two only differs by a constant.
+///
+/// ```ignore
+/// aoc::example_tests!(Solution<'static>);
+/// aoc::example_tests!(Solution<'static>, part_one);
+/// ```
+#[macro_export]
+macro_rules! example_tests {
+    ($solution:ty) => {
+        $crate::example_tests!($solution, part_one, part_two);
+    };
+    ($solution:ty, $($part:ident),+ $(,)?) => {
+        #[cfg(test)]
+        mod example_tests {
+            #[allow(unused_imports)]
+            use super::*;
+
+            const README: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/README.md"));
+
+            #[test]
+            fn parse() {
+                $crate::__private::check_parse::<$solution>(README);
+            }
+
+            $(
+                #[test]
+                fn $part() {
+                    $crate::__private::check_part::<$solution>(README, stringify!($part));
+                }
+            )+
+        }
+    };
+}
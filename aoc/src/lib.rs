@@ -1,26 +1,281 @@
-use std::{fmt, time::Instant};
+#[cfg(feature = "count-allocs")]
+mod alloc;
+mod answer;
+mod bench;
+mod error;
+mod examples;
+mod measure;
+mod reference;
+mod registry;
+pub mod safe;
+mod verify;
+pub use answer::{Answer, IntoAnswer};
+pub use error::ParseError;
+pub use examples::{examples, Example};
+pub use measure::{AllocStats, Measurement, Phase, Probe};
+pub use reference::{compare, leak_input, Difference, Reference};
+pub use registry::{Day, DAYS};
+pub use verify::{verify, Answers, Mismatch};
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::examples::{check_parse, check_part};
+    pub use crate::reference::check_examples;
+    pub use linkme;
+}
 
 pub trait Parser<'a>: Sized + Challenge {
     fn parse(input: &'a str) -> nom::IResult<&'a str, Self>;
 
+    fn try_parse(input: &'a str) -> Result<Self, ParseError> {
+        match Self::parse(input) {
+            Ok((_, challenge)) => Ok(challenge),
+            Err(err) => Err(ParseError::from_nom(input, err)),
+        }
+    }
+
     fn must_parse(input: &'a str) -> Self {
-        Self::parse(input).unwrap().1
+        match Self::try_parse(input) {
+            Ok(challenge) => challenge,
+            Err(err) => panic!("could not parse input\n{err}"),
+        }
+    }
+}
+
+pub trait Challenge: Sized {
+    /// Phases that shouldn't allocate at all.
+    /// [`check`] fails if one does while the `assert-alloc-free` feature is on.
+    const ALLOC_FREE: &'static [Phase] = &[];
+
+    /// Whether [`solve`](Challenge::solve) parses the input a second time for part two.
+    ///
+    /// Timings of both parts together, like the `complete` bench or the `all` runner's,
+    /// include that second parse, so they can't be compared with a day that parses once.
+    /// Override it when [`solve_both`](Challenge::solve_both) always solves both parts.
+    const REPARSES: bool = true;
+
+    fn part_one(self) -> impl IntoAnswer;
+
+    fn part_two(self) -> impl IntoAnswer;
+
+    /// Solves both parts in a single pass, for challenges where most of the work is shared.
+    ///
+    /// The default hands the challenge back, so that the parts get solved separately.
+    fn solve_both(self) -> Result<Run, Self> {
+        Err(self)
+    }
+
+    /// Solves both parts, calling `parse` whenever a fresh copy of the challenge is needed.
+    ///
+    /// Both parts consume the challenge, so by default each one gets its own parse
+    /// rather than requiring `Clone`, see [`REPARSES`](Challenge::REPARSES).
+    /// [`ChallengeRef`] solutions only parse once.
+    /// Uses [`solve_both`](Challenge::solve_both) when the challenge provides it.
+    fn solve<E>(mut parse: impl FnMut() -> Result<Self, E>) -> Result<Run, E> {
+        let part_one = match parse()?.solve_both() {
+            Ok(run) => return Ok(run),
+            Err(challenge) => challenge.part_one().into_answer(),
+        };
+        Ok(Run {
+            part_one,
+            part_two: parse()?.part_two().into_answer(),
+        })
     }
 }
 
-pub trait Challenge {
-    fn part_one(self) -> impl fmt::Display;
+/// A challenge whose parts only need to borrow the parsed input,
+/// so both can run off of the same parse.
+///
+/// Every `ChallengeRef` is also a [`Challenge`].
+pub trait ChallengeRef {
+    /// See [`Challenge::ALLOC_FREE`]
+    const ALLOC_FREE: &'static [Phase] = &[];
+
+    fn part_one(&self) -> impl IntoAnswer;
 
-    fn part_two(self) -> impl fmt::Display;
+    fn part_two(&self) -> impl IntoAnswer;
+
+    /// See [`Challenge::solve_both`]. Returns `None` when the parts should be solved separately.
+    fn solve_both(&self) -> Option<Run> {
+        None
+    }
 }
 
-pub fn check<'a, C: Parser<'a> + Clone>(input: &'a str) {
-    let start = Instant::now();
-    let challenge = C::must_parse(input);
-    let p1 = challenge.clone().part_one();
-    let p2 = challenge.part_two();
-    println!("took: {:?}", start.elapsed());
+impl<C: ChallengeRef> Challenge for C {
+    const ALLOC_FREE: &'static [Phase] = <C as ChallengeRef>::ALLOC_FREE;
+    const REPARSES: bool = false;
+
+    fn part_one(self) -> impl IntoAnswer {
+        ChallengeRef::part_one(&self).into_answer()
+    }
+
+    fn part_two(self) -> impl IntoAnswer {
+        ChallengeRef::part_two(&self).into_answer()
+    }
+
+    fn solve_both(self) -> Result<Run, Self> {
+        ChallengeRef::solve_both(&self).ok_or(self)
+    }
+
+    fn solve<E>(mut parse: impl FnMut() -> Result<Self, E>) -> Result<Run, E> {
+        let challenge = parse()?;
+        if let Some(run) = ChallengeRef::solve_both(&challenge) {
+            return Ok(run);
+        }
+        Ok(Run {
+            part_one: ChallengeRef::part_one(&challenge).into_answer(),
+            part_two: ChallengeRef::part_two(&challenge).into_answer(),
+        })
+    }
+}
+
+/// The answers to both parts of a challenge.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Run {
+    pub part_one: Answer,
+    pub part_two: Answer,
+}
+
+pub fn run<'a, C: Parser<'a>>(input: &'a str) -> Result<Run, ParseError> {
+    C::solve(|| C::try_parse(input))
+}
+
+pub fn run_part_one<'a, C: Parser<'a>>(input: &'a str) -> Result<Answer, ParseError> {
+    Ok(C::try_parse(input)?.part_one().into_answer())
+}
 
-    println!("\tAnswer to part one: {p1}");
-    println!("\tAnswer to part two: {p2}");
+pub fn run_part_two<'a, C: Parser<'a>>(input: &'a str) -> Result<Answer, ParseError> {
+    Ok(C::try_parse(input)?.part_two().into_answer())
+}
+
+/// Runs one phase through the probe, handing back what it returned
+fn probe_phase<T>(probe: &mut dyn Probe, phase: Phase, f: impl FnOnce() -> T) -> T {
+    let mut f = Some(f);
+    let mut output = None;
+    probe.phase(phase, &mut || output = f.take().map(|f| f()));
+    output.expect("probe did not run the phase")
+}
+
+/// Solves both parts, letting `probe` measure each phase on its own.
+///
+/// Part two is given a fresh parse, which isn't counted towards any phase.
+pub fn run_probed<'a, C: Parser<'a>>(
+    input: &'a str,
+    probe: &mut dyn Probe,
+) -> Result<Run, ParseError> {
+    let challenge = probe_phase(probe, Phase::Parse, || C::try_parse(input))?;
+    match probe_phase(probe, Phase::Both, || challenge.solve_both()) {
+        Ok(run) => Ok(run),
+        Err(challenge) => {
+            probe.discard(Phase::Both);
+            let part_one =
+                probe_phase(probe, Phase::PartOne, || challenge.part_one().into_answer());
+            let challenge = C::try_parse(input)?;
+            let part_two =
+                probe_phase(probe, Phase::PartTwo, || challenge.part_two().into_answer());
+            Ok(Run { part_one, part_two })
+        }
+    }
+}
+
+/// Solves both parts, reporting how long each phase took on its own.
+///
+/// Part two is given a fresh parse, which isn't counted towards any phase.
+pub fn check<'a, C: Parser<'a>>(input: &'a str) {
+    let mut phases = Vec::with_capacity(3);
+    let Run { part_one, part_two } = match run_probed::<C>(input, &mut phases) {
+        Ok(run) => run,
+        Err(err) => {
+            eprintln!("{err}");
+            std::process::exit(1);
+        }
+    };
+
+    for m in &phases {
+        println!("{m}");
+    }
+    let total: std::time::Duration = phases.iter().map(|m| m.time).sum();
+    println!("took: {total:?}");
+
+    println!("\tAnswer to part one: {part_one}");
+    warn_overflow(&part_one);
+    println!("\tAnswer to part two: {part_two}");
+    warn_overflow(&part_two);
+
+    #[cfg(feature = "assert-alloc-free")]
+    {
+        let allocating = allocating_phases(C::ALLOC_FREE, &phases);
+        for (phase, count) in &allocating {
+            eprintln!("error: {phase} is marked allocation-free but allocated {count} times");
+        }
+        if !allocating.is_empty() {
+            std::process::exit(1);
+        }
+    }
+}
+
+/// The phases marked allocation-free that allocated anyway, along with how many times
+#[cfg(feature = "assert-alloc-free")]
+fn allocating_phases(alloc_free: &[Phase], phases: &[Measurement]) -> Vec<(Phase, usize)> {
+    phases
+        .iter()
+        .filter_map(|m| {
+            let count = m.allocs.map_or(0, |a| a.count);
+            (count > 0 && alloc_free.contains(&m.phase)).then_some((m.phase, count))
+        })
+        .collect()
+}
+
+fn warn_overflow(answer: &Answer) {
+    if answer.looks_overflowed() {
+        println!("\t\twarning: this looks like it overflowed");
+    }
+}
+
+/// These need the counting allocator, eg `cargo test -p aoc --features assert-alloc-free`
+#[cfg(all(test, feature = "assert-alloc-free"))]
+mod tests {
+    use super::*;
+
+    /// Claims both parts are allocation-free, but part one collects into a `Vec`
+    struct Allocating(u64);
+
+    impl Parser<'_> for Allocating {
+        fn parse(input: &str) -> nom::IResult<&str, Self> {
+            Ok(("", Allocating(input.len() as u64)))
+        }
+    }
+
+    impl Challenge for Allocating {
+        const ALLOC_FREE: &'static [Phase] = &[Phase::PartOne, Phase::PartTwo];
+
+        fn part_one(self) -> impl IntoAnswer {
+            let squares: Vec<u64> =
+                std::hint::black_box((0..self.0 * 100).map(|n| n * n).collect());
+            squares.iter().sum::<u64>()
+        }
+
+        fn part_two(self) -> impl IntoAnswer {
+            self.0
+        }
+    }
+
+    // one test rather than several, since the allocator counts every thread at once
+    #[test]
+    fn allocations() {
+        let mut phases = Vec::new();
+        run_probed::<Allocating>("1234", &mut phases).unwrap();
+        let allocs = |phase| {
+            let m: &Measurement = phases.iter().find(|m| m.phase == phase).unwrap();
+            m.allocs.unwrap()
+        };
+
+        let part_one = allocs(Phase::PartOne);
+        assert!(part_one.count > 0);
+        assert!(part_one.peak_bytes >= 400 * 8, "{part_one:?}");
+        assert_eq!(allocs(Phase::PartTwo), AllocStats::default());
+
+        let allocating = allocating_phases(<Allocating as Challenge>::ALLOC_FREE, &phases);
+        assert_eq!(allocating, [(Phase::PartOne, part_one.count)]);
+    }
 }
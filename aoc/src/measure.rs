@@ -0,0 +1,95 @@
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+/// A step of solving a challenge, as reported by [`check`](crate::check).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+    Parse,
+    PartOne,
+    PartTwo,
+    /// Both parts at once, through [`Challenge::solve_both`](crate::Challenge::solve_both)
+    Both,
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Phase::Parse => "parse",
+            Phase::PartOne => "part one",
+            Phase::PartTwo => "part two",
+            Phase::Both => "both parts",
+        })
+    }
+}
+
+/// Allocations made during one phase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AllocStats {
+    pub count: usize,
+    /// The most memory the phase had allocated at once, on top of what was live before it
+    pub peak_bytes: usize,
+}
+
+/// How long one phase took, and what it allocated if the `count-allocs` feature is on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Measurement {
+    pub phase: Phase,
+    pub time: Duration,
+    pub allocs: Option<AllocStats>,
+}
+
+impl Measurement {
+    /// Measures a single call of `f`, which hands back anything it works out through
+    /// what it captures, as with [`Probe::phase`].
+    pub fn new(phase: Phase, f: impl FnOnce()) -> Self {
+        #[cfg(feature = "count-allocs")]
+        let snapshot = crate::alloc::Snapshot::take();
+        let start = Instant::now();
+        f();
+        let time = start.elapsed();
+
+        #[cfg(feature = "count-allocs")]
+        let allocs = Some(snapshot.stats());
+        #[cfg(not(feature = "count-allocs"))]
+        let allocs = None;
+
+        Measurement {
+            phase,
+            time,
+            allocs,
+        }
+    }
+}
+
+/// Wraps each phase of a run, to measure it, see [`run_probed`](crate::run_probed).
+pub trait Probe {
+    /// Runs one phase by calling `f` exactly once.
+    fn phase(&mut self, phase: Phase, f: &mut dyn FnMut());
+
+    /// Forgets the last phase, when it turned out not to have done anything.
+    fn discard(&mut self, phase: Phase);
+}
+
+impl Probe for Vec<Measurement> {
+    fn phase(&mut self, phase: Phase, f: &mut dyn FnMut()) {
+        self.push(Measurement::new(phase, f));
+    }
+
+    fn discard(&mut self, phase: Phase) {
+        if self.last().is_some_and(|m| m.phase == phase) {
+            self.pop();
+        }
+    }
+}
+
+impl fmt::Display for Measurement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} took: {:?}", self.phase, self.time)?;
+        if let Some(AllocStats { count, peak_bytes }) = self.allocs {
+            write!(f, " ({count} allocations, {peak_bytes} bytes peak)")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,182 @@
+use std::fmt;
+
+use crate::{examples, Answer, IntoAnswer, ParseError, Parser, Phase};
+
+/// A slow but obviously correct solver for a challenge, working straight off the input,
+/// to test the optimised solution against with [`differential_tests!`](crate::differential_tests).
+pub trait Reference {
+    fn part_one(input: &str) -> impl IntoAnswer;
+
+    fn part_two(input: &str) -> impl IntoAnswer;
+}
+
+/// An input on which a solution and its reference disagree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Difference {
+    pub phase: Phase,
+    pub input: String,
+    pub solution: Result<Answer, ParseError>,
+    pub reference: Answer,
+}
+
+impl fmt::Display for Difference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} differs from the reference on\n{}",
+            self.phase, self.input
+        )?;
+        match &self.solution {
+            Ok(answer) => writeln!(f, "solution: {answer}")?,
+            Err(err) => write!(f, "solution: {err}")?,
+        }
+        write!(f, "reference: {}", self.reference)
+    }
+}
+
+/// Solves the given parts of an input with both the solution and its reference,
+/// returning the first part they disagree on.
+///
+/// ```
+/// use aoc::{Challenge, Phase, Reference};
+///
+/// struct Sum(Vec<u32>);
+///
+/// impl<'a> aoc::Parser<'a> for Sum {
+///     fn parse(input: &'a str) -> nom::IResult<&'a str, Self> {
+///         let numbers = input.split_whitespace().map(|n| n.parse().unwrap());
+///         Ok(("", Sum(numbers.collect())))
+///     }
+/// }
+///
+/// impl Challenge for Sum {
+///     fn part_one(self) -> impl aoc::IntoAnswer {
+///         self.0.iter().sum::<u32>()
+///     }
+///
+///     fn part_two(self) -> impl aoc::IntoAnswer {
+///         // "optimised" by assuming there are exactly three numbers
+///         self.0[0] * self.0[1] * self.0[2]
+///     }
+/// }
+///
+/// struct Naive;
+///
+/// impl Reference for Naive {
+///     fn part_one(input: &str) -> impl aoc::IntoAnswer {
+///         input.split_whitespace().map(|n| n.parse::<u32>().unwrap()).sum::<u32>()
+///     }
+///
+///     fn part_two(input: &str) -> impl aoc::IntoAnswer {
+///         input.split_whitespace().map(|n| n.parse::<u32>().unwrap()).product::<u32>()
+///     }
+/// }
+///
+/// let parts = [Phase::PartOne, Phase::PartTwo];
+/// assert!(aoc::compare::<Sum, Naive>("1 2 3", &parts).is_ok());
+///
+/// let difference = aoc::compare::<Sum, Naive>("1 2 3 4", &parts).unwrap_err();
+/// assert_eq!(difference.phase, Phase::PartTwo);
+/// assert_eq!(difference.reference, aoc::Answer::Int(24));
+/// ```
+pub fn compare<'a, C: Parser<'a>, R: Reference>(
+    input: &'a str,
+    parts: &[Phase],
+) -> Result<(), Box<Difference>> {
+    for &phase in parts {
+        let (solution, reference) = match phase {
+            Phase::PartOne => (
+                C::try_parse(input).map(|c| c.part_one().into_answer()),
+                R::part_one(input).into_answer(),
+            ),
+            Phase::PartTwo => (
+                C::try_parse(input).map(|c| c.part_two().into_answer()),
+                R::part_two(input).into_answer(),
+            ),
+            Phase::Parse | Phase::Both => continue,
+        };
+        if solution.as_ref() != Ok(&reference) {
+            return Err(Box::new(Difference {
+                phase,
+                input: input.to_owned(),
+                solution,
+                reference,
+            }));
+        }
+    }
+    Ok(())
+}
+
+/// Leaks a generated input for a property test to solve. Solutions may borrow from their
+/// input and are registered as `'static`, so the input has to outlive them.
+pub fn leak_input(input: String) -> &'static str {
+    Box::leak(input.into_boxed_str())
+}
+
+#[doc(hidden)]
+pub fn check_examples<C: Parser<'static>, R: Reference>(readme: &'static str) {
+    let examples = examples(readme);
+    assert!(!examples.is_empty(), "README has no examples");
+    for example in examples {
+        // only compare the parts the example is meant for
+        let parts: Vec<_> = [Phase::PartOne, Phase::PartTwo]
+            .into_iter()
+            .filter(|&phase| example.answer(phase).is_some())
+            .collect();
+        if let Err(difference) = compare::<C, R>(example.input, &parts) {
+            panic!("{difference}");
+        }
+    }
+}
+
+/// Generates tests that compare a solution against its [`Reference`],
+/// on the examples in the crate's `README.md` and optionally on generated inputs.
+///
+/// Generated inputs come from a function returning a proptest `Strategy` for the input text.
+/// They need `proptest` as a dev-dependency, and are compared on both parts,
+/// reporting the smallest input proptest can find that the two disagree on.
+///
+/// ```ignore
+/// aoc::differential_tests!(Solution<'static>, reference::Naive);
+/// aoc::differential_tests!(Solution, reference::Naive, inputs = reference::inputs);
+/// ```
+#[macro_export]
+macro_rules! differential_tests {
+    ($solution:ty, $reference:ty $(,)?) => {
+        $crate::differential_tests!(@examples $solution, $reference);
+    };
+    ($solution:ty, $reference:ty, inputs = $inputs:path $(,)?) => {
+        $crate::differential_tests!(@examples $solution, $reference);
+
+        #[cfg(test)]
+        mod differential_generated {
+            #[allow(unused_imports)]
+            use super::*;
+
+            ::proptest::proptest! {
+                #[test]
+                fn generated(input in $inputs()) {
+                    let input = $crate::leak_input(input);
+                    let parts = [$crate::Phase::PartOne, $crate::Phase::PartTwo];
+                    if let Err(difference) = $crate::compare::<$solution, $reference>(input, &parts) {
+                        ::proptest::prop_assert!(false, "{}", difference);
+                    }
+                }
+            }
+        }
+    };
+    (@examples $solution:ty, $reference:ty) => {
+        #[cfg(test)]
+        mod differential_tests {
+            #[allow(unused_imports)]
+            use super::*;
+
+            const README: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/README.md"));
+
+            #[test]
+            fn examples() {
+                $crate::__private::check_examples::<$solution, $reference>(README);
+            }
+        }
+    };
+}
@@ -0,0 +1,71 @@
+use std::io;
+
+use crate::{Answer, Answers, ParseError, Probe, Run};
+
+/// Every solution registered with [`solution!`](crate::solution) in the crates linked
+/// into this binary, in no particular order.
+#[linkme::distributed_slice]
+pub static DAYS: [Day];
+
+/// A type-erased solution, as registered by [`solution!`](crate::solution).
+#[derive(Debug)]
+pub struct Day {
+    pub year: i32,
+    pub day: u32,
+    pub title: &'static str,
+    /// The challenge crate's directory, holding its `README.md`
+    pub dir: &'static str,
+    /// Parses the input and solves both parts
+    pub run: fn(&'static str) -> Result<Run, ParseError>,
+    /// Parses the input and solves only part one
+    pub part_one: fn(&'static str) -> Result<Answer, ParseError>,
+    /// Parses the input and solves only part two
+    pub part_two: fn(&'static str) -> Result<Answer, ParseError>,
+    /// Parses the input and solves both parts, measuring each phase with the probe
+    pub run_probed: fn(&'static str, &mut dyn Probe) -> Result<Run, ParseError>,
+    /// Whether `run` parses the input again for part two, see [`crate::Challenge::REPARSES`]
+    pub reparses: bool,
+}
+
+impl Day {
+    /// All registered days, ordered by year then day.
+    pub fn all() -> Vec<&'static Day> {
+        let mut days: Vec<_> = DAYS.iter().collect();
+        days.sort_by_key(|d| (d.year, d.day));
+        days
+    }
+
+    /// The crate name, eg `day07`
+    pub fn name(&self) -> String {
+        format!("day{:02}", self.day)
+    }
+
+    /// The known answers from the crate's `answers.txt`, if it has one.
+    pub fn answers(&self) -> io::Result<Option<Answers>> {
+        Answers::load(self.dir)
+    }
+}
+
+/// Registers a challenge so that runners over every day can find it.
+///
+/// ```ignore
+/// aoc::solution!(Solution<'static>, year = 2023, day = 12, title = "Hot Springs");
+/// ```
+#[macro_export]
+macro_rules! solution {
+    ($solution:ty, year = $year:literal, day = $day:literal, title = $title:literal $(,)?) => {
+        #[$crate::__private::linkme::distributed_slice($crate::DAYS)]
+        #[linkme(crate = $crate::__private::linkme)]
+        static __AOC_DAY: $crate::Day = $crate::Day {
+            year: $year,
+            day: $day,
+            title: $title,
+            dir: env!("CARGO_MANIFEST_DIR"),
+            run: $crate::run::<$solution>,
+            part_one: $crate::run_part_one::<$solution>,
+            part_two: $crate::run_part_two::<$solution>,
+            run_probed: $crate::run_probed::<$solution>,
+            reparses: <$solution as $crate::Challenge>::REPARSES,
+        };
+    };
+}
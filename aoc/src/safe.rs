@@ -0,0 +1,175 @@
+//! Input accessors for the hand-rolled parsers, checked when the `safe` feature is on.
+//!
+//! By default they compile down to the indexing they replace, trusting the input
+//! to be well-formed. With `safe`, every access is bounds checked and malformed input
+//! becomes a parse error instead of a panic or undefined behaviour, so the whole suite
+//! can run under Miri or be fuzzed:
+//!
+//! ```sh
+//! cargo test --workspace --features aoc/safe
+//! cargo +nightly miri test -p day19 --features aoc/safe
+//! ```
+//!
+//! ```
+//! use aoc::safe;
+//!
+//! fn id(input: &str) -> nom::IResult<&str, [u8; 2]> {
+//!     Ok((safe::skip(input, 2)?, safe::bytes(input)?))
+//! }
+//!
+//! assert_eq!(id("ab -> cd"), Ok((" -> cd", *b"ab")));
+//! if safe::ENABLED {
+//!     assert!(id("a").is_err());
+//! }
+//! ```
+
+use nom::error::ErrorKind;
+
+/// Whether the `safe` feature is on, for checks that don't fit the accessors below.
+pub const ENABLED: bool = cfg!(feature = "safe");
+
+pub type Error<'a> = nom::Err<nom::error::Error<&'a str>>;
+
+/// A parse error at the start of `input`.
+///
+/// It's reported as where `input` starts within the whole input, so pass the rest of the
+/// input from the offending byte rather than the whole of it. The kinds read as what was
+/// expected there, see [`ParseError::from_nom`](crate::ParseError::from_nom).
+pub fn error(input: &str, kind: ErrorKind) -> Error<'_> {
+    nom::Err::Error(nom::error::Error::new(input, kind))
+}
+
+/// A parse error at byte `offset` of `input`, or the start of the character it falls in
+#[cold]
+pub fn error_at(input: &str, offset: usize, kind: ErrorKind) -> Error<'_> {
+    let mut offset = offset.min(input.len());
+    while !input.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    error(&input[offset..], kind)
+}
+
+/// Fails with `kind` at the start of `input` if `valid` doesn't hold.
+/// Only checked when the `safe` feature is on.
+#[inline(always)]
+pub fn ensure(valid: bool, input: &str, kind: ErrorKind) -> Result<(), Error<'_>> {
+    if ENABLED && !valid {
+        return Err(error(input, kind));
+    }
+    Ok(())
+}
+
+/// Fails with `kind` at byte `offset` of `input` if `valid` doesn't hold,
+/// for parsers that work on bytes. Only checked when the `safe` feature is on.
+#[inline(always)]
+pub fn ensure_at(
+    valid: bool,
+    input: &str,
+    offset: usize,
+    kind: ErrorKind,
+) -> Result<(), Error<'_>> {
+    if ENABLED && !valid {
+        return Err(error_at(input, offset, kind));
+    }
+    Ok(())
+}
+
+#[cold]
+fn eof(input: &str) -> Error<'_> {
+    error(&input[input.len()..], ErrorKind::Eof)
+}
+
+/// `input.as_bytes()[i]`
+#[inline(always)]
+pub fn byte(input: &str, i: usize) -> Result<u8, Error<'_>> {
+    if ENABLED {
+        input.as_bytes().get(i).copied().ok_or_else(|| eof(input))
+    } else {
+        Ok(input.as_bytes()[i])
+    }
+}
+
+/// The first `N` bytes of `input`
+#[inline(always)]
+pub fn bytes<const N: usize>(input: &str) -> Result<[u8; N], Error<'_>> {
+    match input.as_bytes().first_chunk() {
+        Some(bytes) => Ok(*bytes),
+        None if ENABLED => Err(eof(input)),
+        None => panic!("expected {N} more bytes of input"),
+    }
+}
+
+/// The `N` bytes of `input` from byte `i`, for parsers that walk `input.as_bytes()`
+#[inline(always)]
+pub fn bytes_at<const N: usize>(input: &str, i: usize) -> Result<[u8; N], Error<'_>> {
+    match input.as_bytes().get(i..).and_then(<[u8]>::first_chunk) {
+        Some(bytes) => Ok(*bytes),
+        None if ENABLED => Err(eof(input)),
+        None => panic!("expected {N} more bytes of input"),
+    }
+}
+
+/// `&input[n..]`
+#[inline(always)]
+pub fn skip(input: &str, n: usize) -> Result<&str, Error<'_>> {
+    if ENABLED {
+        input.get(n..).ok_or_else(|| eof(input))
+    } else {
+        Ok(&input[n..])
+    }
+}
+
+/// `input.get_unchecked(n..)`
+///
+/// # Safety
+///
+/// `n` must be at most `input.len()` and on a char boundary.
+/// The `safe` feature checks this, returning an error when it doesn't hold.
+#[inline(always)]
+pub unsafe fn skip_unchecked(input: &str, n: usize) -> Result<&str, Error<'_>> {
+    if ENABLED {
+        input.get(n..).ok_or_else(|| eof(input))
+    } else {
+        // SAFETY: upheld by the caller
+        Ok(unsafe { input.get_unchecked(n..) })
+    }
+}
+
+/// Reinterprets the bytes of `input` as a slice of `T`, typically a `#[repr(u8)]` enum
+/// whose discriminants are the characters of a grid.
+///
+/// # Safety
+///
+/// `T` must be one byte, and every byte of `input` must be a valid `T`.
+/// The `safe` feature checks each byte against `valid`, which must accept only valid `T`s,
+/// and fails at the first one it rejects.
+#[inline(always)]
+pub unsafe fn cast<T: Copy>(input: &str, valid: impl Fn(u8) -> bool) -> Result<&[T], Error<'_>> {
+    const { assert!(std::mem::size_of::<T>() == 1) };
+    if ENABLED {
+        if let Some(i) = input.bytes().position(|b| !valid(b)) {
+            return Err(error_at(input, i, ErrorKind::Char));
+        }
+    }
+    // SAFETY: T is one byte, and the caller guarantees every byte is a valid T
+    Ok(unsafe { std::slice::from_raw_parts(input.as_ptr().cast(), input.len()) })
+}
+
+/// Fails at the first line of `input` that isn't `width` bytes long, its newline included,
+/// for the grids whose size is picked from the length of the input.
+/// Only checked when the `safe` feature is on.
+pub fn lines(input: &str, width: usize) -> Result<(), Error<'_>> {
+    if !ENABLED || width == 0 {
+        return Ok(());
+    }
+    for (row, line) in input.as_bytes().chunks(width).enumerate() {
+        let start = row * width;
+        match line.iter().position(|&b| b == b'\n') {
+            Some(i) if i + 1 == width => {}
+            Some(i) => return Err(error_at(input, start + i, ErrorKind::LengthValue)),
+            None if line.len() < width => return Err(eof(input)),
+            None => return Err(error_at(input, start + width - 1, ErrorKind::CrLf)),
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use std::{fmt, fs, io, path::Path};
+
+use crate::{Answer, Phase, Run};
+
+/// The known answers to a challenge, as stored in the `answers.txt` next to its `README.md`.
+///
+/// The file holds part one on the first line and part two on the second.
+/// A blank or missing line means that answer isn't known yet.
+/// ASCII art can't be compared, so its line should be left blank.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+impl Answers {
+    pub fn parse(s: &str) -> Self {
+        let mut lines = s.lines().map(str::trim).map(|line| {
+            Some(line)
+                .filter(|line| !line.is_empty())
+                .map(str::to_owned)
+        });
+        Answers {
+            part_one: lines.next().flatten(),
+            part_two: lines.next().flatten(),
+        }
+    }
+
+    /// Reads the `answers.txt` in a challenge crate's directory, if there is one.
+    pub fn load(dir: impl AsRef<Path>) -> io::Result<Option<Self>> {
+        match fs::read_to_string(dir.as_ref().join("answers.txt")) {
+            Ok(s) => Ok(Some(Self::parse(&s))),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Compares one part's answer against the known one, if that is known.
+    pub fn check(&self, phase: Phase, actual: &Answer) -> Option<Mismatch> {
+        let expected = match phase {
+            Phase::PartOne => self.part_one.as_ref()?,
+            Phase::PartTwo => self.part_two.as_ref()?,
+            Phase::Parse | Phase::Both => return None,
+        };
+        (actual.canonical().as_ref() != Some(expected)).then(|| Mismatch {
+            phase,
+            expected: expected.clone(),
+            actual: actual.clone(),
+        })
+    }
+}
+
+/// A part whose answer differs from the known one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub phase: Phase,
+    pub expected: String,
+    pub actual: Answer,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: expected {}, got {}",
+            self.phase, self.expected, self.actual
+        )
+    }
+}
+
+/// Compares a run against the known answers, returning every part that differs.
+///
+/// ```
+/// use aoc::{Answer, Answers, Run};
+/// let known = Answers::parse("142\n\n");
+/// let run = |part_one| Run { part_one: Answer::Int(part_one), part_two: Answer::Int(281) };
+/// assert!(aoc::verify(&run(142), &known).is_ok());
+///
+/// let mismatches = aoc::verify(&run(143), &known).unwrap_err();
+/// assert_eq!(mismatches[0].to_string(), "part one: expected 142, got 143");
+/// ```
+pub fn verify(run: &Run, known: &Answers) -> Result<(), Vec<Mismatch>> {
+    let mismatches: Vec<_> = [
+        known.check(Phase::PartOne, &run.part_one),
+        known.check(Phase::PartTwo, &run.part_two),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(mismatches)
+    }
+}
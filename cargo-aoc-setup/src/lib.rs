@@ -1,15 +1,16 @@
-use std::{cell::RefCell, path::Path};
+use std::{cell::RefCell, ops::Range, path::Path};
 
-use aoc_client::{base_url_for_day, get_input, get_page_html};
+use aoc_client::{AocClient, AocError};
 use comrak::{
     nodes::{Ast, AstNode, NodeCode, NodeCodeBlock, NodeHeading, NodeLink, NodeList, NodeValue},
     Arena,
 };
 use scraper::{Html, Selector};
+use toml_edit::{value, DocumentMut, InlineTable};
 use url::Url;
 use walkdir::WalkDir;
 
-pub fn create_project(day: i32) {
+pub fn create_project(client: &AocClient, day: i32) -> Result<(), AocError> {
     assert!(day >= 1);
     assert!(day <= 25);
 
@@ -22,8 +23,10 @@ pub fn create_project(day: i32) {
     let path = challenges.join(&project_name);
 
     if path.exists() {
-        return;
+        return Ok(());
     }
+    // fetch the input first, so a failed request doesn't leave a half made project behind
+    let input = client.input(day)?;
 
     for entry in WalkDir::new(&template_path) {
         let entry = entry.unwrap();
@@ -32,16 +35,44 @@ pub fn create_project(day: i32) {
             let out = path.join(rel);
             let content = fs_err::read_to_string(entry.path())
                 .unwrap()
-                .replace("day00", &project_name);
+                .replace("day00", &project_name)
+                .replace("day = 0,", &format!("day = {day},"));
             fs_err::create_dir_all(out.parent().unwrap()).unwrap();
             fs_err::write(out, content).unwrap();
         }
     }
 
-    fs_err::write(path.join("input.txt"), get_input(day)).unwrap();
+    fs_err::write(path.join("input.txt"), input).unwrap();
+
+    // link the new day into the `all` runner, wherever its `[dependencies]` are
+    let all = challenges.join("all").join("Cargo.toml");
+    let mut manifest: DocumentMut = fs_err::read_to_string(&all).unwrap().parse().unwrap();
+    let mut dependency = InlineTable::new();
+    dependency.insert("path", format!("../{project_name}").into());
+    manifest["dependencies"][&project_name] = value(dependency);
+    fs_err::write(all, manifest.to_string()).unwrap();
+
+    // and give it a fuzz target for its parser
+    let fuzz = challenges.parent().unwrap().join("fuzz");
+    let mut manifest = fs_err::read_to_string(fuzz.join("Cargo.toml")).unwrap();
+    manifest += &format!(
+        "\n[dependencies.{project_name}]\npath = \"../challenges/{project_name}\"\n\n\
+         [[bin]]\nname = \"{project_name}\"\npath = \"fuzz_targets/{project_name}.rs\"\n\
+         test = false\ndoc = false\nbench = false\n"
+    );
+    fs_err::write(fuzz.join("Cargo.toml"), manifest).unwrap();
+    fs_err::write(
+        fuzz.join("fuzz_targets").join(format!("{project_name}.rs")),
+        format!(
+            "#![no_main]\n\nlibfuzzer_sys::fuzz_target!(|data: &[u8]| \
+             fuzz::parse::<{project_name}::Solution>(data));\n"
+        ),
+    )
+    .unwrap();
+    Ok(())
 }
 
-pub fn get_project_description(day: i32) {
+pub fn get_project_description(client: &AocClient, day: i32) -> Result<(), AocError> {
     assert!(day >= 1);
     assert!(day <= 25);
 
@@ -52,10 +83,99 @@ pub fn get_project_description(day: i32) {
         .join("challenges");
     let path = challenges.join(project_name);
 
-    let data = get_page_html(day);
-    let url = base_url_for_day(day);
+    let data = client.page(day)?;
+    let url = client.day_url(day);
+
+    // fill in the registered title, eg "--- Day 1: Trebuchet?! ---"
+    if let Some(title) = page_title(&data) {
+        let lib = path.join("src").join("lib.rs");
+        let source = fs_err::read_to_string(&lib)
+            .unwrap()
+            .replace("title = \"\"", &format!("title = {title:?}"));
+        fs_err::write(lib, source).unwrap();
+    }
+
+    // part two turns up as the same README with more on the end, so the annotations
+    // added to the examples by hand have to be kept, or their tests would go missing
+    let readme = path.join("README.md");
+    let mut markdown = page_to_markdown(&data, &url);
+    if let Ok(old) = fs_err::read_to_string(&readme) {
+        markdown = keep_annotations(&old, &markdown);
+    }
+    fs_err::write(readme, markdown)?;
+    Ok(())
+}
+
+/// Copies the info strings of the fenced code blocks in `old`,
+/// eg ```` ```example part_one=142 ````, onto the blocks in `new` with the same contents.
+pub fn keep_annotations(old: &str, new: &str) -> String {
+    let mut annotated: Vec<_> = fences(old)
+        .into_iter()
+        .filter(|&(_, info, _)| !info.is_empty())
+        .collect();
+
+    let mut markdown = String::with_capacity(new.len());
+    let mut offset = 0;
+    for (opening, info, content) in fences(new) {
+        let Some(i) = annotated
+            .iter()
+            .position(|&(_, _, old_content)| old_content == content)
+            .filter(|_| info.is_empty())
+        else {
+            continue;
+        };
+        let (_, old_info, _) = annotated.remove(i);
+        markdown += &new[offset..opening.start];
+        markdown += &format!("```{old_info}\n");
+        offset = opening.end;
+    }
+    markdown += &new[offset..];
+    markdown
+}
+
+/// The fenced code blocks in some markdown, as the span of their opening line,
+/// their info string and their contents
+fn fences(text: &str) -> Vec<(Range<usize>, &str, &str)> {
+    let mut fences = Vec::new();
+    let mut open = None;
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+
+        let line = line.trim_end();
+        match open {
+            None => {
+                if let Some(info) = line.strip_prefix("```") {
+                    open = Some((start..offset, info.trim()));
+                }
+            }
+            Some((opening, info)) if line == "```" => {
+                let content = &text[opening.end..start];
+                fences.push((opening, info, content));
+                open = None;
+            }
+            Some(_) => {}
+        }
+    }
+    fences
+}
+
+/// The puzzle's title, without the day, eg "Trebuchet?!" for "--- Day 1: Trebuchet?! ---"
+pub fn page_title(page: &str) -> Option<String> {
+    let html = Html::parse_document(page);
+    let heading = Selector::parse("article.day-desc h2").unwrap();
+    let heading = html.select(&heading).next()?.text().collect::<String>();
+    let title = heading.trim_matches(['-', ' ']);
+    let title = title.split_once(": ").map_or(title, |(_, t)| t);
+    Some(title.to_owned())
+}
+
+/// Converts the puzzle descriptions on a day's page into Markdown,
+/// resolving links against the page's `url`.
+pub fn page_to_markdown(page: &str, url: &str) -> String {
+    let html = Html::parse_document(page);
 
-    let html = Html::parse_document(&data);
     let selector = Selector::parse("article.day-desc").unwrap();
 
     let arena = Arena::new();
@@ -169,7 +289,7 @@ pub fn get_project_description(day: i32) {
                         document = section;
                     }
                     scraper::Node::Element(e) if e.name() == "a" => {
-                        let url = Url::parse(&url)
+                        let url = Url::parse(url)
                             .unwrap()
                             .join(e.attr("href").unwrap())
                             .unwrap();
@@ -214,11 +334,7 @@ pub fn get_project_description(day: i32) {
         }
     }
 
-    let mut output = fs_err::OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path.join("README.md"))
-        .unwrap();
+    let mut output = Vec::new();
     comrak::format_commonmark(document, &comrak::Options::default(), &mut output).unwrap();
+    String::from_utf8(output).unwrap()
 }
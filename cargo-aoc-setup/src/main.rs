@@ -1,6 +1,14 @@
+use aoc_client::AocClient;
+
 fn main() {
     let day = time::OffsetDateTime::now_local().unwrap().day() as i32;
 
-    cargo_aoc_setup::create_project(day);
-    cargo_aoc_setup::get_project_description(day);
+    let setup = AocClient::from_env().and_then(|client| {
+        cargo_aoc_setup::create_project(&client, day)?;
+        cargo_aoc_setup::get_project_description(&client, day)
+    });
+    if let Err(err) = setup {
+        eprintln!("error: {err}");
+        std::process::exit(1);
+    }
 }
@@ -0,0 +1,54 @@
+use aoc_client::{
+    stub::{Response, StubServer},
+    AocClient, Cache,
+};
+use cargo_aoc_setup::{keep_annotations, page_title, page_to_markdown};
+
+const PAGE: &str = include_str!("../../aoc-client/tests/fixtures/day01.html");
+
+#[test]
+fn title() {
+    assert_eq!(page_title(PAGE).as_deref(), Some("Trebuchet?!"));
+    assert_eq!(page_title("<main></main>"), None);
+}
+
+#[test]
+fn markdown() {
+    let server = StubServer::start(|request| match request.path.as_str() {
+        "/2023/day/1" => Response::ok(PAGE),
+        _ => Response::status(404),
+    });
+    let mut client = AocClient::new("2023");
+    client.base_url = server.url();
+    client.session = Some("53616c7465645f5f".to_owned());
+    let cache = tempfile::tempdir().unwrap();
+    client.cache = Cache::new(cache.path());
+
+    let page = client.page(1).unwrap();
+    let markdown = page_to_markdown(&page, &client.day_url(1));
+
+    assert!(markdown.starts_with("## \\--- Day 1: Trebuchet?! ---\n"));
+    assert!(markdown.contains("a specific **calibration value** that"));
+    assert!(markdown.contains("```\n1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n"));
+    assert!(markdown.contains("`12`, `38`, `15`, and `77`"));
+    assert!(markdown.contains("Digits may be spelled out."));
+
+    // relative links resolve against the page, absolute ones are kept
+    let weather = format!("[weather machine]({}/2015/day/1)", server.url());
+    assert!(markdown.contains(&weather), "{markdown}");
+    assert!(markdown.contains("[trebuchet](https://en.wikipedia.org/wiki/Trebuchet)"));
+
+    // only the puzzle description is kept, not the answer form around it
+    assert!(!markdown.contains("puzzle input"));
+}
+
+#[test]
+fn annotations() {
+    let markdown = page_to_markdown(PAGE, "https://adventofcode.com/2023/day/1");
+    let annotated = markdown.replacen("```\n1abc2", "```example part_one=142\n1abc2", 1);
+    assert_ne!(annotated, markdown);
+
+    // fetching the page again keeps the annotations added by hand
+    assert_eq!(keep_annotations(&annotated, &markdown), annotated);
+    assert_eq!(keep_annotations("", &markdown), markdown);
+}
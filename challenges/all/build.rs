@@ -0,0 +1,85 @@
+//! Links in every `dayNN` dependency so that their `aoc::solution!` registrations
+//! end up in `aoc::DAYS`. A crate that is never mentioned isn't linked at all.
+//!
+//! So a new day only runs once it's listed under `[dependencies]` in this crate's
+//! `Cargo.toml`. `cargo-aoc-setup` adds it there, anything made by hand needs adding too,
+//! and any `challenges/dayNN` crate that isn't listed is warned about here.
+//!
+//! With the `embed-inputs` feature, each day's `input.txt` that exists at build time
+//! is compiled in as well, so benchmarks don't measure reading files.
+
+use std::{env, fmt::Write, fs, path::Path};
+
+use toml_edit::DocumentMut;
+
+fn main() {
+    println!("cargo:rerun-if-changed=Cargo.toml");
+    let embed = env::var_os("CARGO_FEATURE_EMBED_INPUTS").is_some();
+
+    let manifest: DocumentMut = fs::read_to_string("Cargo.toml").unwrap().parse().unwrap();
+    let dependencies = manifest["dependencies"].as_table_like().unwrap();
+    let mut out = String::new();
+    let mut inputs = String::new();
+    for (name, _) in dependencies.iter() {
+        if is_day(name) {
+            writeln!(out, "extern crate {name};").unwrap();
+
+            let input = Path::new("..").join(name).join("input.txt");
+            if embed {
+                println!("cargo:rerun-if-changed={}", input.display());
+                if input.exists() {
+                    let path = input.canonicalize().unwrap();
+                    writeln!(inputs, "        {name:?} => Some(include_str!({path:?})),").unwrap();
+                }
+            }
+        }
+    }
+
+    writeln!(
+        out,
+        "\n/// The inputs embedded by the `embed-inputs` feature"
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "fn embedded_input(name: &str) -> Option<&'static str> {{"
+    )
+    .unwrap();
+    if inputs.is_empty() {
+        // a `match` with only the `_` arm would trip `clippy::match_single_binding`
+        writeln!(out, "    let _ = name;\n    None\n}}").unwrap();
+    } else {
+        writeln!(
+            out,
+            "    match name {{\n{inputs}        _ => None,\n    }}\n}}"
+        )
+        .unwrap();
+    }
+
+    // a build script can't add dependencies, so the best it can do is point out the missing ones
+    println!("cargo:rerun-if-changed=..");
+    for entry in fs::read_dir("..").unwrap() {
+        let name = entry.unwrap().file_name();
+        let Some(name) = name.to_str() else { continue };
+        // day00 is the template that `cargo-aoc-setup` copies
+        if is_day(name)
+            && name != "day00"
+            && Path::new("..").join(name).join("Cargo.toml").exists()
+            && !dependencies.contains_key(name)
+        {
+            println!(
+                "cargo:warning=challenges/{name} is not a dependency of `all`, so it won't run. \
+                 Add `{name} = {{ path = \"../{name}\" }}` to challenges/all/Cargo.toml"
+            );
+        }
+    }
+
+    let dest = Path::new(&env::var("OUT_DIR").unwrap()).join("days.rs");
+    fs::write(dest, out).unwrap();
+}
+
+/// Whether a crate is named like a day, eg `day07`
+fn is_day(name: &str) -> bool {
+    name.strip_prefix("day")
+        .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+}
@@ -0,0 +1,206 @@
+use std::{
+    collections::BTreeMap,
+    env, fmt, fs, io,
+    path::{Path, PathBuf},
+    process::Command,
+    time::{Duration, SystemTime},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{bench::Stats, setup::Setup, Parts};
+
+/// A saved bench run, to compare later runs against.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Baseline {
+    /// The commit the run was built from, if it was in a git checkout
+    pub commit: Option<String>,
+    /// Whether the checkout had uncommitted changes
+    pub dirty: bool,
+    pub machine: Machine,
+    /// How the runner was set up, eg pinned or with cold caches
+    #[serde(default)]
+    pub setup: Setup,
+    /// Which parts were solved
+    #[serde(default)]
+    pub parts: Parts,
+    /// When the run was recorded, in seconds since the Unix epoch
+    pub recorded: u64,
+    pub days: BTreeMap<String, Stats>,
+    /// Each day's divan benches by name, if they were run
+    #[serde(default)]
+    pub benches: BTreeMap<String, BTreeMap<String, Stats>>,
+}
+
+/// What the run was on, since timings from different machines can't be compared.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Machine {
+    pub hostname: String,
+    pub cpu: String,
+    pub os: String,
+    pub arch: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Regressed,
+    Improved,
+    Unchanged,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Verdict::Regressed => "regressed",
+            Verdict::Improved => "improved",
+            Verdict::Unchanged => "unchanged",
+        })
+    }
+}
+
+/// How one day's mean moved relative to the baseline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Change {
+    pub baseline: Duration,
+    /// The relative change in the mean, eg `0.1` for 10% slower
+    pub ratio: f64,
+    pub verdict: Verdict,
+}
+
+impl Change {
+    /// Compares two runs of a day.
+    /// A change only counts if it's bigger than `noise`, as a fraction of the baseline's mean,
+    /// and bigger than twice the standard error of the difference between the means.
+    pub fn new(baseline: &Stats, current: &Stats, noise: f64) -> Self {
+        let diff = current.mean_ns - baseline.mean_ns;
+        let ratio = diff / baseline.mean_ns;
+        let standard_error = (baseline.stddev_ns.powi(2) / baseline.samples.max(1) as f64
+            + current.stddev_ns.powi(2) / current.samples.max(1) as f64)
+            .sqrt();
+
+        let verdict = if ratio.abs() <= noise || diff.abs() <= 2.0 * standard_error {
+            Verdict::Unchanged
+        } else if diff > 0.0 {
+            Verdict::Regressed
+        } else {
+            Verdict::Improved
+        };
+        Change {
+            baseline: baseline.mean(),
+            ratio,
+            verdict,
+        }
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:+.1}%", self.ratio * 100.0)?;
+        if self.verdict != Verdict::Unchanged {
+            write!(f, " {}", self.verdict)?;
+        }
+        Ok(())
+    }
+}
+
+impl Baseline {
+    /// Records a run along with the current commit and machine.
+    pub fn new(days: BTreeMap<String, Stats>, setup: Setup, parts: Parts) -> Self {
+        let recorded = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let (commit, dirty) = commit();
+        Baseline {
+            commit,
+            dirty,
+            machine: Machine::current(),
+            setup,
+            parts,
+            recorded,
+            days,
+            benches: BTreeMap::new(),
+        }
+    }
+
+    /// Where baselines are kept, `$AOC_BASELINE_DIR` or `.baselines` in the workspace.
+    /// Not under `target`, so that `cargo clean` doesn't throw them away.
+    pub fn dir() -> PathBuf {
+        match env::var_os("AOC_BASELINE_DIR") {
+            Some(dir) => PathBuf::from(dir),
+            None => workspace().join(".baselines"),
+        }
+    }
+
+    fn path(name: &str) -> PathBuf {
+        Self::dir().join(format!("{name}.json"))
+    }
+
+    pub fn load(name: &str) -> io::Result<Self> {
+        let json = fs::read_to_string(Self::path(name))?;
+        serde_json::from_str(&json).map_err(io::Error::from)
+    }
+
+    /// Saves the baseline, returning where it was written.
+    pub fn save(&self, name: &str) -> io::Result<PathBuf> {
+        let path = Self::path(name);
+        fs::create_dir_all(Self::dir())?;
+        fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(path)
+    }
+
+    /// Describes where the baseline came from, eg `abc1234 (dirty) on host`
+    pub fn describe(&self) -> String {
+        let commit = match &self.commit {
+            Some(commit) => commit.get(..10).unwrap_or(commit),
+            None => "unknown commit",
+        };
+        let dirty = if self.dirty { " (dirty)" } else { "" };
+        format!("{commit}{dirty} on {}", self.machine.hostname)
+    }
+}
+
+impl Machine {
+    pub fn current() -> Self {
+        let hostname = fs::read_to_string("/etc/hostname")
+            .ok()
+            .or_else(|| env::var("HOSTNAME").ok())
+            .or_else(|| env::var("COMPUTERNAME").ok())
+            .map_or_else(|| "unknown".to_owned(), |s| s.trim().to_owned());
+        let cpu = fs::read_to_string("/proc/cpuinfo")
+            .ok()
+            .and_then(|info| {
+                info.lines()
+                    .find_map(|line| line.strip_prefix("model name")?.split_once(':'))
+                    .map(|(_, model)| model.trim().to_owned())
+            })
+            .unwrap_or_else(|| "unknown".to_owned());
+        Machine {
+            hostname,
+            cpu,
+            os: env::consts::OS.to_owned(),
+            arch: env::consts::ARCH.to_owned(),
+        }
+    }
+}
+
+fn workspace() -> &'static Path {
+    Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/../.."))
+}
+
+/// The checked out commit, and whether there are uncommitted changes
+fn commit() -> (Option<String>, bool) {
+    let git = |args: &[&str]| {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(workspace())
+            .output()
+            .ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_owned())
+    };
+    let commit = git(&["rev-parse", "HEAD"]);
+    let dirty = git(&["status", "--porcelain"]).is_some_and(|status| !status.is_empty());
+    (commit, dirty)
+}
@@ -0,0 +1,149 @@
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::setup::Evictor;
+
+/// Samples should take at least this long, so the timer's overhead and precision don't matter
+const MIN_SAMPLE_TIME: Duration = Duration::from_micros(50);
+/// Outlier rejection and percentiles need a few samples to mean anything
+const MIN_SAMPLES: u32 = 10;
+
+/// How to sample each day.
+pub struct Config {
+    /// How long to run a day before sampling it, to warm the caches and branch predictors
+    pub warmup: Duration,
+    /// Roughly how long to spend sampling a day, unless `samples` is set
+    pub budget: Duration,
+    /// Take exactly this many samples instead
+    pub samples: Option<u32>,
+    /// Evicts the caches before each sample, for cold-cache runs
+    pub evictor: Option<Evictor>,
+}
+
+/// Times a function the way divan does:
+/// each sample runs it several times and records the mean time per iteration.
+///
+/// With an evictor, each sample runs it only once, straight after evicting the caches.
+pub fn sample(config: &mut Config, mut f: impl FnMut()) -> Stats {
+    let start = Instant::now();
+    let mut warmup_iterations = 0u32;
+    while warmup_iterations == 0 || start.elapsed() < config.warmup {
+        f();
+        warmup_iterations += 1;
+    }
+    let estimate = start.elapsed() / warmup_iterations;
+
+    let (sample_size, overhead) = match &mut config.evictor {
+        Some(evictor) => {
+            let start = Instant::now();
+            evictor.evict();
+            (1, start.elapsed())
+        }
+        None => {
+            let size = MIN_SAMPLE_TIME.as_nanos() / estimate.as_nanos().max(1);
+            (size.max(1) as u32, Duration::ZERO)
+        }
+    };
+    let count = config.samples.unwrap_or_else(|| {
+        let per_sample = (estimate * sample_size + overhead).as_secs_f64();
+        ((config.budget.as_secs_f64() / per_sample) as u32).max(MIN_SAMPLES)
+    });
+
+    let times: Vec<_> = (0..count)
+        .map(|_| {
+            if let Some(evictor) = &mut config.evictor {
+                evictor.evict();
+            }
+            let start = Instant::now();
+            for _ in 0..sample_size {
+                f();
+            }
+            start.elapsed() / sample_size
+        })
+        .collect();
+    Stats::new(&times, sample_size)
+}
+
+/// Timings for one day, in nanoseconds per iteration.
+///
+/// Samples outside Tukey's fences, 1.5 times the interquartile range past the quartiles,
+/// are dropped as outliers before anything else is worked out.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    /// How many samples were kept
+    pub samples: usize,
+    /// How many samples were dropped as outliers
+    #[serde(default)]
+    pub outliers: usize,
+    /// How many iterations each sample averaged over
+    #[serde(default)]
+    pub sample_size: u32,
+    pub mean_ns: f64,
+    pub median_ns: f64,
+    #[serde(default)]
+    pub p95_ns: f64,
+    pub min_ns: f64,
+    #[serde(default)]
+    pub max_ns: f64,
+    pub stddev_ns: f64,
+}
+
+/// The nearest-rank percentile of sorted samples
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    match sorted.len() {
+        0 => 0.0,
+        n => sorted[((p * n as f64).ceil() as usize).clamp(1, n) - 1],
+    }
+}
+
+impl Stats {
+    pub fn new(samples: &[Duration], sample_size: u32) -> Self {
+        let mut ns: Vec<_> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        ns.sort_by(f64::total_cmp);
+
+        let total = ns.len();
+        if total >= 4 {
+            let (q1, q3) = (percentile(&ns, 0.25), percentile(&ns, 0.75));
+            let fence = 1.5 * (q3 - q1);
+            ns.retain(|&x| (q1 - fence..=q3 + fence).contains(&x));
+        }
+
+        let n = ns.len();
+        let mean = ns.iter().sum::<f64>() / n.max(1) as f64;
+        let median = match n {
+            0 => 0.0,
+            _ if n % 2 == 0 => (ns[n / 2 - 1] + ns[n / 2]) / 2.0,
+            _ => ns[n / 2],
+        };
+        let variance = ns.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n.max(2) - 1) as f64;
+
+        Stats {
+            samples: n,
+            outliers: total - n,
+            sample_size,
+            mean_ns: mean,
+            median_ns: median,
+            p95_ns: percentile(&ns, 0.95),
+            min_ns: ns.first().copied().unwrap_or(0.0),
+            max_ns: ns.last().copied().unwrap_or(0.0),
+            stddev_ns: variance.sqrt(),
+        }
+    }
+
+    pub fn mean(&self) -> Duration {
+        Duration::from_nanos(self.mean_ns as u64)
+    }
+
+    pub fn median(&self) -> Duration {
+        Duration::from_nanos(self.median_ns as u64)
+    }
+
+    pub fn p95(&self) -> Duration {
+        Duration::from_nanos(self.p95_ns as u64)
+    }
+
+    pub fn stddev(&self) -> Duration {
+        Duration::from_nanos(self.stddev_ns as u64)
+    }
+}
@@ -0,0 +1,244 @@
+use std::io;
+
+use aoc::{Day, Phase, Probe};
+use serde::Serialize;
+
+/// Hardware counts for one phase, averaged over the runs.
+/// A counter the CPU or kernel wouldn't give us is left as `None`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct Counts {
+    pub instructions: Option<u64>,
+    pub cycles: Option<u64>,
+    pub branch_misses: Option<u64>,
+    pub cache_misses: Option<u64>,
+}
+
+impl Counts {
+    /// Instructions per cycle
+    pub fn ipc(&self) -> Option<f64> {
+        Some(self.instructions? as f64 / self.cycles?.max(1) as f64)
+    }
+
+    /// The counts over every phase of a run
+    pub fn total(phases: &[(Phase, Counts)]) -> Option<Counts> {
+        let mut phases = phases.iter().map(|(_, counts)| *counts);
+        let first = phases.next()?;
+        Some(phases.fold(first, |total, counts| total.zip(&counts, |a, b| a + b)))
+    }
+
+    /// Applies `f` to each counter, pairing it with the same one from `other`
+    fn zip(&self, other: &Counts, f: impl Fn(u64, u64) -> u64) -> Counts {
+        let f = |a: Option<u64>, b: Option<u64>| Some(f(a?, b?));
+        Counts {
+            instructions: f(self.instructions, other.instructions),
+            cycles: f(self.cycles, other.cycles),
+            branch_misses: f(self.branch_misses, other.branch_misses),
+            cache_misses: f(self.cache_misses, other.cache_misses),
+        }
+    }
+}
+
+/// Reads the counters around each phase of a run.
+struct Recorder<'a> {
+    counters: &'a mut Counters,
+    phases: Vec<(Phase, Counts)>,
+}
+
+impl Probe for Recorder<'_> {
+    fn phase(&mut self, phase: Phase, f: &mut dyn FnMut()) {
+        self.counters.start();
+        f();
+        self.phases.push((phase, self.counters.stop()));
+    }
+
+    fn discard(&mut self, phase: Phase) {
+        if self.phases.last().is_some_and(|&(p, _)| p == phase) {
+            self.phases.pop();
+        }
+    }
+}
+
+impl Counters {
+    /// Counts each phase of a day, averaged over `runs` runs after one to warm up.
+    pub fn measure(&mut self, day: &Day, input: &'static str, runs: u64) -> Vec<(Phase, Counts)> {
+        let runs = runs.max(1);
+        let mut totals: Vec<(Phase, Counts)> = Vec::new();
+        for run in 0..=runs {
+            let mut recorder = Recorder {
+                counters: self,
+                phases: Vec::with_capacity(3),
+            };
+            // The answers were already checked, so only the counts matter here
+            let _ = (day.run_probed)(input, &mut recorder);
+            if run == 0 {
+                continue;
+            }
+            for (phase, counts) in recorder.phases {
+                match totals.iter_mut().find(|(p, _)| *p == phase) {
+                    Some((_, total)) => *total = total.zip(&counts, |a, b| a + b),
+                    None => totals.push((phase, counts)),
+                }
+            }
+        }
+        let runs = Counts {
+            instructions: Some(runs),
+            cycles: Some(runs),
+            branch_misses: Some(runs),
+            cache_misses: Some(runs),
+        };
+        totals
+            .into_iter()
+            .map(|(phase, total)| (phase, total.zip(&runs, |total, runs| total / runs)))
+            .collect()
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub use linux::Counters;
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::{
+        fs::File,
+        io::{self, Read},
+        os::fd::{AsRawFd, FromRawFd},
+    };
+
+    use perf_event_open_sys::{bindings, ioctls, perf_event_open};
+
+    use super::Counts;
+
+    const EVENTS: [u32; 4] = [
+        bindings::perf_hw_id_PERF_COUNT_HW_INSTRUCTIONS,
+        bindings::perf_hw_id_PERF_COUNT_HW_CPU_CYCLES,
+        bindings::perf_hw_id_PERF_COUNT_HW_BRANCH_MISSES,
+        bindings::perf_hw_id_PERF_COUNT_HW_CACHE_MISSES,
+    ];
+
+    /// Hardware counters for this process, read through `perf_event_open`.
+    pub struct Counters {
+        events: [Option<File>; 4],
+    }
+
+    fn open(event: u32) -> io::Result<File> {
+        let mut attr = bindings::perf_event_attr {
+            type_: bindings::perf_type_id_PERF_TYPE_HARDWARE,
+            size: std::mem::size_of::<bindings::perf_event_attr>() as u32,
+            config: event.into(),
+            read_format: (bindings::perf_event_read_format_PERF_FORMAT_TOTAL_TIME_ENABLED
+                | bindings::perf_event_read_format_PERF_FORMAT_TOTAL_TIME_RUNNING)
+                .into(),
+            ..Default::default()
+        };
+        attr.set_disabled(1);
+        // Count the threads rayon spawns too, as long as they start after this
+        attr.set_inherit(1);
+        attr.set_exclude_kernel(1);
+        attr.set_exclude_hv(1);
+
+        // SAFETY: attr is a valid perf_event_attr, and this process (pid 0) on any cpu (-1)
+        // is always a valid target
+        let fd =
+            unsafe { perf_event_open(&mut attr, 0, -1, -1, bindings::PERF_FLAG_FD_CLOEXEC.into()) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // SAFETY: the fd was just opened, and nothing else owns it
+        Ok(unsafe { File::from_raw_fd(fd) })
+    }
+
+    /// Reads a counter, scaled up if the kernel had to share the hardware with other events
+    fn read(mut file: &File) -> Option<u64> {
+        let mut buf = [0; 24];
+        file.read_exact(&mut buf).ok()?;
+        let [value, enabled, running] =
+            [0, 8, 16].map(|i| u64::from_ne_bytes(buf[i..i + 8].try_into().unwrap()));
+        match running {
+            0 => None,
+            _ => Some((value as u128 * enabled as u128 / running as u128) as u64),
+        }
+    }
+
+    impl Counters {
+        /// Opens every counter it can, failing only if none of them could be opened.
+        ///
+        /// Only threads started afterwards are counted, so this should be called first thing.
+        pub fn open() -> io::Result<Self> {
+            let mut first_err = None;
+            let events = EVENTS.map(|event| {
+                open(event)
+                    .map_err(|err| {
+                        first_err.get_or_insert(err);
+                    })
+                    .ok()
+            });
+            match first_err {
+                Some(err) if events.iter().all(Option::is_none) => Err(err),
+                _ => Ok(Counters { events }),
+            }
+        }
+
+        pub(super) fn start(&mut self) {
+            for file in self.events.iter().flatten() {
+                // SAFETY: these are perf event fds, which is what the ioctls expect
+                unsafe {
+                    ioctls::RESET(file.as_raw_fd(), 0);
+                    ioctls::ENABLE(file.as_raw_fd(), 0);
+                }
+            }
+        }
+
+        pub(super) fn stop(&mut self) -> Counts {
+            for file in self.events.iter().flatten() {
+                // SAFETY: as in start
+                unsafe { ioctls::DISABLE(file.as_raw_fd(), 0) };
+            }
+            let [instructions, cycles, branch_misses, cache_misses] =
+                self.events.each_ref().map(|e| e.as_ref().and_then(read));
+            Counts {
+                instructions,
+                cycles,
+                branch_misses,
+                cache_misses,
+            }
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub use unsupported::Counters;
+
+#[cfg(not(target_os = "linux"))]
+mod unsupported {
+    use std::io;
+
+    use super::Counts;
+
+    /// Hardware counters are only read on Linux, so this never opens.
+    pub struct Counters(());
+
+    impl Counters {
+        pub fn open() -> io::Result<Self> {
+            Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "hardware counters are only supported on Linux",
+            ))
+        }
+
+        pub(super) fn start(&mut self) {}
+
+        pub(super) fn stop(&mut self) -> Counts {
+            Counts::default()
+        }
+    }
+}
+
+/// Explains why the counters couldn't be opened, since the OS error alone rarely says.
+pub fn unavailable(err: &io::Error) -> String {
+    let hint = match err.kind() {
+        io::ErrorKind::PermissionDenied => ", try lowering /proc/sys/kernel/perf_event_paranoid",
+        io::ErrorKind::NotFound => ", the CPU or VM may not expose a PMU",
+        _ => "",
+    };
+    format!("hardware counters are unavailable: {err}{hint}")
+}
@@ -0,0 +1,120 @@
+use std::{collections::BTreeMap, env, io, process::Command};
+
+use aoc::Day;
+
+use crate::bench::Stats;
+
+/// Runs a day's divan benches, returning each bench's timings by name.
+///
+/// Divan only prints a table, so the timings are read back out of it. The table is
+/// passed on to stderr as well, so that it can still be read.
+pub fn run(day: &Day) -> io::Result<BTreeMap<String, Stats>> {
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let output = Command::new(cargo)
+        .args([
+            "bench",
+            "--quiet",
+            "--package",
+            &day.name(),
+            "--bench",
+            "benches",
+        ])
+        .args(["--", "--color", "never"])
+        .current_dir(day.dir)
+        .output()?;
+    let table = String::from_utf8_lossy(&output.stdout);
+    eprint!("{}{table}", String::from_utf8_lossy(&output.stderr));
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "cargo bench exited with {}",
+            output.status
+        )));
+    }
+    Ok(parse(&table))
+}
+
+/// Reads the benches out of divan's table, eg
+///
+/// ```text
+/// benches           fastest       │ slowest       │ median        │ mean          │ samples │ iters
+/// ├─ complete       222 ns        │ 585.2 ns      │ 222.4 ns      │ 294.8 ns      │ 5       │ 25
+/// ╰─ parse          1.1 µs        │ 2.4 µs        │ 1.2 µs        │ 1.3 µs        │ 5       │ 25
+/// ```
+///
+/// Divan reports neither the spread nor the 95th percentile, so both are left at zero,
+/// which leaves only `--noise` to decide whether a bench changed.
+pub fn parse(table: &str) -> BTreeMap<String, Stats> {
+    table.lines().filter_map(row).collect()
+}
+
+fn row(line: &str) -> Option<(String, Stats)> {
+    let line = line
+        .strip_prefix("├─ ")
+        .or_else(|| line.strip_prefix("╰─ "))?;
+    let columns: Vec<_> = line.split('│').map(str::trim).collect();
+    let [first, slowest, median, mean, samples, iters] = columns[..] else {
+        return None;
+    };
+    let (name, fastest) = first.split_once(' ')?;
+    let samples: usize = samples.parse().ok()?;
+    let iters: usize = iters.parse().ok()?;
+
+    let stats = Stats {
+        samples,
+        outliers: 0,
+        sample_size: (iters / samples.max(1)) as u32,
+        mean_ns: nanos(mean)?,
+        median_ns: nanos(median)?,
+        p95_ns: 0.0,
+        min_ns: nanos(fastest.trim())?,
+        max_ns: nanos(slowest)?,
+        stddev_ns: 0.0,
+    };
+    Some((name.to_owned(), stats))
+}
+
+/// Reads a time like `1.5 µs` as nanoseconds
+fn nanos(time: &str) -> Option<f64> {
+    let (value, unit) = time.split_once(' ')?;
+    let scale = match unit {
+        "ps" => 1e-3,
+        "ns" => 1.0,
+        "µs" => 1e3,
+        "ms" => 1e6,
+        "s" => 1e9,
+        _ => return None,
+    };
+    Some(value.parse::<f64>().ok()? * scale)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table() {
+        let table = "\
+Timer precision: 20 ns
+benches           fastest       │ slowest       │ median        │ mean          │ samples │ iters
+├─ complete       222 ns        │ 585.2 ns      │ 222.4 ns      │ 294.8 ns      │ 5       │ 25
+│                 4.5 Mitem/s   │ 1.7 Mitem/s   │ 4.4 Mitem/s   │ 3.3 Mitem/s   │         │
+╰─ parse          1.1 µs        │ 2.4 ms        │ 1.2 µs        │ 1.5 s         │ 4       │ 400
+";
+        let benches = parse(table);
+        assert_eq!(benches.len(), 2);
+
+        let complete = benches["complete"];
+        assert_eq!(complete.samples, 5);
+        assert_eq!(complete.sample_size, 5);
+        assert_eq!(complete.min_ns, 222.0);
+        assert_eq!(complete.max_ns, 585.2);
+        assert_eq!(complete.median_ns, 222.4);
+        assert_eq!(complete.mean_ns, 294.8);
+
+        let parse = benches["parse"];
+        assert_eq!(parse.sample_size, 100);
+        assert_eq!(parse.min_ns, 1100.0);
+        assert_eq!(parse.max_ns, 2.4e6);
+        assert_eq!(parse.mean_ns, 1.5e9);
+    }
+}
@@ -1,114 +1,397 @@
-use std::time::{Duration, Instant};
-
-use aoc::Parser;
-
-static DAY01: &str = include_str!("../../day01/input.txt");
-static DAY02: &str = include_str!("../../day02/input.txt");
-static DAY03: &str = include_str!("../../day03/input.txt");
-static DAY04: &str = include_str!("../../day04/input.txt");
-static DAY05: &str = include_str!("../../day05/input.txt");
-static DAY06: &str = include_str!("../../day06/input.txt");
-static DAY07: &str = include_str!("../../day07/input.txt");
-static DAY08: &str = include_str!("../../day08/input.txt");
-static DAY09: &str = include_str!("../../day09/input.txt");
-static DAY10: &str = include_str!("../../day10/input.txt");
-static DAY11: &str = include_str!("../../day11/input.txt");
-static DAY12: &str = include_str!("../../day12/input.txt");
-static DAY13: &str = include_str!("../../day13/input.txt");
-static DAY14: &str = include_str!("../../day14/input.txt");
-static DAY15: &str = include_str!("../../day15/input.txt");
-static DAY16: &str = include_str!("../../day16/input.txt");
-static DAY17: &str = include_str!("../../day17/input.txt");
-static DAY18: &str = include_str!("../../day18/input.txt");
-// static DAY19: &str = include_str!("../../day19/input.txt");
-// static DAY20: &str = include_str!("../../day20/input.txt");
-// static DAY21: &str = include_str!("../../day21/input.txt");
-// static DAY22: &str = include_str!("../../day22/input.txt");
-// static DAY23: &str = include_str!("../../day23/input.txt");
-// static DAY24: &str = include_str!("../../day24/input.txt");
-// static DAY25: &str = include_str!("../../day25/input.txt");
-
-#[allow(unused_mut)]
+use std::{
+    collections::BTreeMap,
+    env, fmt, fs, io,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use aoc::{Answer, Day, ParseError, Phase};
+use clap::{Parser, ValueEnum};
+use serde::{Deserialize, Serialize};
+
+mod baseline;
+mod bench;
+mod counters;
+mod divan;
+mod parallel;
+mod report;
+mod setup;
+use baseline::{Baseline, Change, Verdict};
+use report::{Format, Report, Row};
+use setup::{Cache, Evictor, Setup};
+
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
+
+/// How many runs the hardware counters are averaged over
+const COUNTER_RUNS: u64 = 10;
+/// How many times the calendar is run at once with `--parallel`, unless `--repeat` is given
+const PARALLEL_RUNS: u32 = 10;
+
+/// Solves every registered day, checks the known answers, then benchmarks them all
+#[derive(Parser)]
+struct Args {
+    /// Days to run, eg `1 5 12`. Runs every registered day by default
+    days: Vec<u32>,
+
+    /// Which parts to solve
+    #[arg(long, value_enum, default_value_t = Parts::Both)]
+    parts: Parts,
+
+    /// Take this many samples of each day, rather than filling `--time`
+    #[arg(long, short = 'n', value_parser = clap::value_parser!(u32).range(1..))]
+    repeat: Option<u32>,
+
+    /// Roughly how long sampling should take, in seconds, split evenly between the days
+    #[arg(long, default_value_t = 5.0)]
+    time: f64,
+
+    /// How long to run each day before sampling it, in seconds
+    #[arg(long, default_value_t = 0.05)]
+    warmup: f64,
+
+    /// Pin the runner and rayon's threads to these cores, eg `0,2-3`
+    #[arg(long, value_name = "LIST", value_parser = setup::parse_cores)]
+    cores: Option<setup::Cores>,
+
+    /// Limit rayon to a single thread
+    #[arg(long)]
+    single_threaded: bool,
+
+    /// Whether to evict the caches before each sample
+    #[arg(long, value_enum, default_value_t = Cache::Warm)]
+    cache: Cache,
+
+    /// How much memory to write to evict the caches, in MiB.
+    /// Defaults to twice the largest cache
+    #[arg(long, value_name = "MIB")]
+    evict_size: Option<usize>,
+
+    /// Only solve each day once
+    #[arg(long, conflicts_with_all = ["save_baseline", "baseline", "divan"])]
+    no_bench: bool,
+
+    /// Save the bench loop's timings under this name, along with the commit and machine
+    #[arg(long, value_name = "NAME")]
+    save_baseline: Option<String>,
+
+    /// Compare the bench loop's timings against a saved baseline
+    #[arg(long, value_name = "NAME")]
+    baseline: Option<String>,
+
+    /// Exit with an error if any day regressed against the baseline
+    #[arg(long, requires = "baseline")]
+    fail_on_regression: bool,
+
+    /// Also run each day's divan benches, saving and comparing them along with the bench loop
+    #[arg(long)]
+    divan: bool,
+
+    /// Also run every day at once on rayon's pool, comparing wall time against CPU time
+    #[arg(long)]
+    parallel: bool,
+
+    /// Read hardware performance counters for each phase, on Linux
+    #[arg(long)]
+    counters: bool,
+
+    /// How much a day's mean has to move, as a fraction, before it counts as a change
+    #[arg(long, default_value_t = 0.05)]
+    noise: f64,
+
+    /// How to print the results
+    #[arg(long, value_enum, default_value_t = Format::Table)]
+    format: Format,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Parts {
+    One,
+    Two,
+    #[default]
+    Both,
+}
+
+impl fmt::Display for Parts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Parts::One => "part one",
+            Parts::Two => "part two",
+            Parts::Both => "both parts",
+        })
+    }
+}
+
 fn main() {
+    let args = Args::parse();
+    // Before anything can start a thread, so that the counters follow it
+    let mut counters = match args.counters.then(counters::Counters::open) {
+        Some(Ok(counters)) => Some(counters),
+        Some(Err(err)) => {
+            eprintln!("warning: {}", counters::unavailable(&err));
+            None
+        }
+        None => None,
+    };
+
+    let setup = Setup {
+        cores: args.cores.clone(),
+        threads: args.single_threaded.then_some(1),
+        cache: args.cache,
+    };
+    if let Err(err) = setup.apply() {
+        eprintln!("error: {err}");
+        std::process::exit(1);
+    }
+
+    let mut days = Vec::new();
+    for day in Day::all() {
+        if !args.days.is_empty() && !args.days.contains(&day.day) {
+            continue;
+        }
+        match input(day) {
+            Ok(input) => days.push((day, input)),
+            Err(err) => eprintln!("{}: skipping, {err}", day.name()),
+        }
+    }
+    for &d in &args.days {
+        if !days.iter().any(|(day, _)| day.day == d) {
+            eprintln!("day{d:02}: not registered or has no input");
+        }
+    }
+
     let start = Instant::now();
-    let mut results = Vec::<Duration>::with_capacity(25);
-    results.push(check::<day01::Solution>(DAY01));
-    results.push(check::<day02::Solution>(DAY02));
-    results.push(check::<day03::Solution>(DAY03));
-    results.push(check::<day04::Solution>(DAY04));
-    results.push(check::<day05::Solution>(DAY05));
-    results.push(check::<day06::Solution>(DAY06));
-    results.push(check::<day07::Solution>(DAY07));
-    results.push(check::<day08::Solution>(DAY08));
-    results.push(check::<day09::Solution>(DAY09));
-    results.push(check::<day10::Solution>(DAY10));
-    results.push(check::<day11::Solution>(DAY11));
-    results.push(check::<day12::Solution>(DAY12));
-    results.push(check::<day13::Solution>(DAY13));
-    results.push(check::<day14::Solution>(DAY14));
-    results.push(check::<day15::Solution>(DAY15));
-    results.push(check::<day16::Solution>(DAY16));
-    results.push(check::<day17::Solution>(DAY17));
-    results.push(check::<day18::Solution>(DAY18));
-    // results.push(check::<day19::Solution>(DAY19));
-    // results.push(check::<day20::Solution>(DAY20));
-    // results.push(check::<day21::Solution>(DAY21));
-    // results.push(check::<day22::Solution>(DAY22));
-    // results.push(check::<day23::Solution>(DAY23));
-    // results.push(check::<day24::Solution>(DAY24));
-    // results.push(check::<day25::Solution>(DAY25));
-
-    let elapsed = start.elapsed();
-    println!("Running {} days took {elapsed:?}", results.len());
-    println!("{results:#?}");
+    let mut rows = Vec::with_capacity(days.len());
+    let mut failed = false;
+    for &(day, input) in &days {
+        let start = Instant::now();
+        let answers = std::hint::black_box(solve(day, input, args.parts));
+        let first_run = start.elapsed();
+
+        let (part_one, part_two) = match answers {
+            Ok(answers) => answers,
+            Err(err) => {
+                eprintln!("{}: {err}", day.name());
+                failed = true;
+                continue;
+            }
+        };
+        failed |= !verify(day, &part_one, &part_two);
+        rows.push(Row {
+            day: day.name(),
+            title: day.title,
+            part_one,
+            part_two,
+            reparsed: day.reparses && args.parts == Parts::Both,
+            first_run,
+            stats: None,
+            change: None,
+            counters: Vec::new(),
+        });
+    }
 
+    let mut report = Report {
+        rows,
+        total: start.elapsed(),
+        benched: false,
+        setup,
+        calendar: None,
+    };
+
+    if failed {
+        report.print(args.format);
+        eprintln!("some days did not match their known answers");
+        std::process::exit(1);
+    }
+
+    if let Some(counters) = &mut counters {
+        for (&(day, input), row) in days.iter().zip(&mut report.rows) {
+            row.counters = counters.measure(day, input, COUNTER_RUNS);
+        }
+    }
+
+    if args.parallel {
+        let runs = args.repeat.unwrap_or(PARALLEL_RUNS) as usize;
+        report.calendar = Some(parallel::measure(&days, runs, |day, input| {
+            let _ = std::hint::black_box(solve(day, std::hint::black_box(input), args.parts));
+        }));
+    }
+
+    if args.no_bench {
+        report.print(args.format);
+        return;
+    }
+
+    // Load the baseline first, so a typo doesn't waste a whole bench loop
+    let baseline = args.baseline.as_deref().map(|name| {
+        Baseline::load(name).unwrap_or_else(|err| {
+            eprintln!("could not load baseline {name}: {err}");
+            std::process::exit(1);
+        })
+    });
+
+    let mut config = bench::Config {
+        warmup: Duration::from_secs_f64(args.warmup),
+        budget: Duration::from_secs_f64(args.time / days.len().max(1) as f64),
+        samples: args.repeat,
+        evictor: (args.cache == Cache::Cold)
+            .then(|| Evictor::new(args.evict_size.map(|mib| mib << 20))),
+    };
     let start = Instant::now();
-    let n = std::time::Duration::from_secs(5).as_nanos() / elapsed.as_nanos() * 2;
-    for _ in 0..n {
-        bench::<day01::Solution>(DAY01);
-        bench::<day02::Solution>(DAY02);
-        bench::<day03::Solution>(DAY03);
-        bench::<day04::Solution>(DAY04);
-        bench::<day05::Solution>(DAY05);
-        bench::<day06::Solution>(DAY06);
-        bench::<day07::Solution>(DAY07);
-        bench::<day08::Solution>(DAY08);
-        bench::<day09::Solution>(DAY09);
-        bench::<day10::Solution>(DAY10);
-        bench::<day11::Solution>(DAY11);
-        bench::<day12::Solution>(DAY12);
-        bench::<day13::Solution>(DAY13);
-        bench::<day14::Solution>(DAY14);
-        bench::<day15::Solution>(DAY15);
-        bench::<day16::Solution>(DAY16);
-        bench::<day17::Solution>(DAY17);
-        bench::<day18::Solution>(DAY18);
-        // bench::<day19::Solution>(DAY19);
-        // bench::<day20::Solution>(DAY20);
-        // bench::<day21::Solution>(DAY21);
-        // bench::<day22::Solution>(DAY22);
-        // bench::<day23::Solution>(DAY23);
-        // bench::<day24::Solution>(DAY24);
-        // bench::<day25::Solution>(DAY25);
-    }
-    let elapsed = start.elapsed();
-
-    println!("Running {} days {n} times took {elapsed:?}", results.len());
-    println!("Average {:?}", elapsed / n as u32);
+    let mut stats = BTreeMap::new();
+    for (&(day, input), row) in days.iter().zip(&mut report.rows) {
+        let day_stats = bench::sample(&mut config, || {
+            let _ = std::hint::black_box(solve(day, std::hint::black_box(input), args.parts));
+        });
+        row.stats = Some(day_stats);
+        stats.insert(row.day.clone(), day_stats);
+    }
+    report.total = start.elapsed();
+    report.benched = true;
+
+    let mut benches = BTreeMap::new();
+    if args.divan {
+        for &(day, _) in &days {
+            match divan::run(day) {
+                Ok(day_benches) => {
+                    benches.insert(day.name(), day_benches);
+                }
+                Err(err) => eprintln!("{}: could not run the divan benches, {err}", day.name()),
+            }
+        }
+    }
+
+    let mut regressed = false;
+    if let Some(baseline) = &baseline {
+        let machine = baseline::Machine::current();
+        if baseline.machine != machine {
+            eprintln!(
+                "warning: baseline was recorded on {} ({}), this is {} ({})",
+                baseline.machine.hostname, baseline.machine.cpu, machine.hostname, machine.cpu
+            );
+        }
+        if baseline.setup != report.setup {
+            eprintln!(
+                "warning: baseline was {}, this is {}",
+                baseline.setup, report.setup
+            );
+        }
+        if baseline.parts != args.parts {
+            eprintln!(
+                "warning: baseline solved {}, this solves {}",
+                baseline.parts, args.parts
+            );
+        }
+        eprintln!("comparing against {}", baseline.describe());
+
+        for row in &mut report.rows {
+            let Some(before) = baseline.days.get(&row.day) else {
+                continue;
+            };
+            let change = Change::new(before, &stats[&row.day], args.noise);
+            regressed |= change.verdict == Verdict::Regressed;
+            row.change = Some(change);
+        }
+
+        for (day, day_benches) in &benches {
+            let Some(before) = baseline.benches.get(day) else {
+                continue;
+            };
+            for (name, now) in day_benches {
+                let Some(before) = before.get(name) else {
+                    continue;
+                };
+                let change = Change::new(before, now, args.noise);
+                regressed |= change.verdict == Verdict::Regressed;
+                eprintln!("{day} {name}: {change} against {:?}", change.baseline);
+            }
+        }
+    }
+
+    report.print(args.format);
+
+    if let Some(name) = &args.save_baseline {
+        let baseline = Baseline {
+            benches,
+            ..Baseline::new(stats, report.setup.clone(), args.parts)
+        };
+        match baseline.save(name) {
+            Ok(path) => eprintln!("saved baseline to {}", path.display()),
+            Err(err) => {
+                eprintln!("could not save baseline {name}: {err}");
+                std::process::exit(1);
+            }
+        }
+    }
+    if regressed {
+        eprintln!("some days regressed against the baseline");
+        if args.fail_on_regression {
+            std::process::exit(1);
+        }
+    }
 }
 
-#[allow(dead_code)]
-fn check<C: Parser<'static> + Clone>(input: &'static str) -> Duration {
-    let start = Instant::now();
-    let challenge = C::must_parse(input);
-    std::hint::black_box(challenge.clone().part_one().to_string());
-    std::hint::black_box(challenge.part_two().to_string());
-    start.elapsed()
+/// Finds the day's input, either embedded by the `embed-inputs` feature or read from
+/// `$AOC_INPUT_DIR/dayNN.txt`, falling back to the `input.txt` in the day's crate.
+fn input(day: &Day) -> Result<&'static str, String> {
+    if let Some(input) = embedded_input(&day.name()) {
+        return Ok(input);
+    }
+
+    let fallback = Path::new(day.dir).join("input.txt");
+    let path = match env::var_os("AOC_INPUT_DIR") {
+        Some(dir) => {
+            let path = PathBuf::from(dir).join(format!("{}.txt", day.name()));
+            if path.exists() {
+                path
+            } else {
+                fallback
+            }
+        }
+        None => fallback,
+    };
+    match fs::read_to_string(&path) {
+        Ok(input) => Ok(input.leak()),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            Err(format!("no input at {}", path.display()))
+        }
+        Err(err) => Err(format!("could not read {}: {err}", path.display())),
+    }
+}
+
+type Answers = (Option<Answer>, Option<Answer>);
+
+fn solve(day: &Day, input: &'static str, parts: Parts) -> Result<Answers, ParseError> {
+    Ok(match parts {
+        Parts::One => (Some((day.part_one)(input)?), None),
+        Parts::Two => (None, Some((day.part_two)(input)?)),
+        Parts::Both => {
+            let run = (day.run)(input)?;
+            (Some(run.part_one), Some(run.part_two))
+        }
+    })
 }
 
-#[allow(dead_code)]
-fn bench<C: Parser<'static> + Clone>(input: &'static str) {
-    let challenge = C::must_parse(std::hint::black_box(input));
-    std::hint::black_box(challenge.clone().part_one().to_string());
-    std::hint::black_box(challenge.part_two().to_string());
+/// Reports any difference from the day's known answers, returning whether it matched.
+fn verify(day: &Day, part_one: &Option<Answer>, part_two: &Option<Answer>) -> bool {
+    let known = match day.answers() {
+        Ok(Some(known)) => known,
+        Ok(None) => {
+            eprintln!("{}: no answers.txt, skipping verification", day.name());
+            return true;
+        }
+        Err(err) => {
+            eprintln!("{}: could not read answers.txt: {err}", day.name());
+            return false;
+        }
+    };
+
+    let mut matched = true;
+    for (phase, answer) in [(Phase::PartOne, part_one), (Phase::PartTwo, part_two)] {
+        if let Some(mismatch) = answer.as_ref().and_then(|a| known.check(phase, a)) {
+            eprintln!("{}: {mismatch}", day.name());
+            matched = false;
+        }
+    }
+    matched
 }
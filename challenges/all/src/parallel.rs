@@ -0,0 +1,114 @@
+use std::time::{Duration, Instant};
+
+use aoc::Day;
+use rayon::prelude::*;
+
+/// How one day fared when the whole calendar ran at once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DayShare {
+    /// How long the day took when run on its own
+    pub wall: Duration,
+    /// The CPU time the day used on its own, across every thread it ran on
+    pub cpu: Option<Duration>,
+    /// When the day finished in the concurrent run, counted from the start of the calendar
+    pub finished: Duration,
+}
+
+/// Running every day concurrently on rayon's pool, medians over several runs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Calendar {
+    pub threads: usize,
+    pub runs: usize,
+    /// How long the whole calendar took, from the first day starting to the last finishing
+    pub wall: Duration,
+    /// The CPU time every thread spent on the calendar put together
+    pub cpu: Option<Duration>,
+    /// How long the days took back to back, each on its own
+    pub sequential: Duration,
+    pub days: Vec<DayShare>,
+}
+
+fn median(mut times: Vec<Duration>) -> Duration {
+    times.sort_unstable();
+    times.get(times.len() / 2).copied().unwrap_or_default()
+}
+
+fn median_cpu(times: Vec<Option<Duration>>) -> Option<Duration> {
+    times.into_iter().collect::<Option<_>>().map(median)
+}
+
+/// Runs each day on its own, then all of them at once, `runs` times over.
+pub fn measure(
+    days: &[(&'static Day, &'static str)],
+    runs: usize,
+    solve: impl Fn(&'static Day, &'static str) + Sync,
+) -> Calendar {
+    let runs = runs.max(1);
+    let mut solo = vec![(Vec::new(), Vec::new()); days.len()];
+    let mut finished = vec![Vec::new(); days.len()];
+    let (mut walls, mut cpus) = (Vec::new(), Vec::new());
+
+    for _ in 0..runs {
+        for (&(day, input), (walls, cpus)) in days.iter().zip(&mut solo) {
+            let (cpu, start) = (cpu_time(), Instant::now());
+            solve(day, input);
+            walls.push(start.elapsed());
+            cpus.push(cpu_since(cpu));
+        }
+
+        let (cpu, start) = (cpu_time(), Instant::now());
+        let times: Vec<_> = days
+            .par_iter()
+            .map(|&(day, input)| {
+                solve(day, input);
+                start.elapsed()
+            })
+            .collect();
+        walls.push(start.elapsed());
+        cpus.push(cpu_since(cpu));
+        for (finished, time) in finished.iter_mut().zip(times) {
+            finished.push(time);
+        }
+    }
+
+    let days: Vec<_> = solo
+        .into_iter()
+        .zip(finished)
+        .map(|((walls, cpus), finished)| DayShare {
+            wall: median(walls),
+            cpu: median_cpu(cpus),
+            finished: median(finished),
+        })
+        .collect();
+    Calendar {
+        threads: rayon::current_num_threads(),
+        runs,
+        wall: median(walls),
+        cpu: median_cpu(cpus),
+        sequential: days.iter().map(|d| d.wall).sum(),
+        days,
+    }
+}
+
+/// The CPU time used by every thread in the process so far
+#[cfg(unix)]
+fn cpu_time() -> Option<Duration> {
+    let mut time = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+    // SAFETY: time is a valid timespec for the clock to write to
+    match unsafe { libc::clock_gettime(libc::CLOCK_PROCESS_CPUTIME_ID, &mut time) } {
+        0 => Some(Duration::new(time.tv_sec as u64, time.tv_nsec as u32)),
+        _ => None,
+    }
+}
+
+#[cfg(not(unix))]
+fn cpu_time() -> Option<Duration> {
+    None
+}
+
+fn cpu_since(start: Option<Duration>) -> Option<Duration> {
+    Some(cpu_time()? - start?)
+}
@@ -0,0 +1,397 @@
+use std::{borrow::Cow, time::Duration};
+
+use aoc::{Answer, Phase};
+
+use crate::{
+    baseline::Change,
+    bench::Stats,
+    counters::Counts,
+    parallel::{Calendar, DayShare},
+    setup::Setup,
+};
+use clap::ValueEnum;
+use serde_json::json;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Aligned columns, for reading
+    Table,
+    /// One object with every day, timings in nanoseconds
+    Json,
+    /// One line per day, timings in nanoseconds
+    Csv,
+}
+
+pub struct Row {
+    pub day: String,
+    pub title: &'static str,
+    pub part_one: Option<Answer>,
+    pub part_two: Option<Answer>,
+    /// Whether the timings include parsing the input twice, once for each part
+    pub reparsed: bool,
+    /// How long solving took the first time, when it was checked
+    pub first_run: Duration,
+    /// Timings from sampling the day, if it was benchmarked
+    pub stats: Option<Stats>,
+    /// How the mean moved against a saved baseline, if one was given
+    pub change: Option<Change>,
+    /// Hardware counts for each phase, if they were read
+    pub counters: Vec<(Phase, Counts)>,
+}
+
+pub struct Report {
+    pub rows: Vec<Row>,
+    /// The time spent benchmarking, or on the first run if the days weren't benchmarked
+    pub total: Duration,
+    pub benched: bool,
+    /// How the runner was set up, eg pinned or with cold caches
+    pub setup: Setup,
+    /// Every day run at once, if it was asked for
+    pub calendar: Option<Calendar>,
+}
+
+fn answer(answer: &Option<Answer>) -> String {
+    answer
+        .as_ref()
+        .map_or_else(String::new, |a| a.to_string().trim().to_owned())
+}
+
+fn nanos(d: Duration) -> u64 {
+    d.as_nanos() as u64
+}
+
+/// A count with its noun, eg `1 day` or `3 days`
+fn plural(n: usize, noun: &str) -> String {
+    match n {
+        1 => format!("{n} {noun}"),
+        _ => format!("{n} {noun}s"),
+    }
+}
+
+fn csv_field(field: &str) -> Cow<'_, str> {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\"")).into()
+    } else {
+        field.into()
+    }
+}
+
+impl Report {
+    pub fn print(&self, format: Format) {
+        match format {
+            Format::Table => self.print_table(),
+            Format::Json => self.print_json(),
+            Format::Csv => self.print_csv(),
+        }
+    }
+
+    fn print_table(&self) {
+        let header = [
+            "day",
+            "part one",
+            "part two",
+            "first run",
+            "median",
+            "mean",
+            "p95",
+            "stddev",
+            "samples",
+            "vs baseline",
+        ];
+        let mut lines = vec![header.map(String::from)];
+        for row in &self.rows {
+            let stat = |f: fn(&Stats) -> Duration| {
+                row.stats
+                    .as_ref()
+                    .map_or_else(String::new, |s| format!("{:?}", f(s)))
+            };
+            lines.push([
+                if row.reparsed {
+                    format!("{}*", row.day)
+                } else {
+                    row.day.clone()
+                },
+                answer(&row.part_one).replace('\n', " "),
+                answer(&row.part_two).replace('\n', " "),
+                format!("{:?}", row.first_run),
+                stat(Stats::median),
+                stat(Stats::mean),
+                stat(Stats::p95),
+                stat(Stats::stddev),
+                row.stats.map_or_else(String::new, |s| {
+                    format!(
+                        "{}/{} x{}",
+                        s.samples,
+                        s.samples + s.outliers,
+                        s.sample_size
+                    )
+                }),
+                row.change.map_or_else(String::new, |c| c.to_string()),
+            ]);
+        }
+
+        let mut widths = [0; 10];
+        for line in &lines {
+            for (width, cell) in widths.iter_mut().zip(line) {
+                *width = (*width).max(cell.len());
+            }
+        }
+        let columns = if self.rows.iter().any(|row| row.change.is_some()) {
+            10
+        } else if self.benched {
+            9
+        } else {
+            4
+        };
+        for line in &lines {
+            let cells: Vec<_> = line[..columns]
+                .iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{cell:width$}"))
+                .collect();
+            println!("{}", cells.join("  ").trim_end());
+        }
+
+        if self.benched {
+            let medians: Duration = self
+                .rows
+                .iter()
+                .filter_map(|row| row.stats)
+                .map(|s| s.median())
+                .sum();
+            println!(
+                "Benchmarking {} took {:?}, their medians add up to {medians:?}",
+                plural(self.rows.len(), "day"),
+                self.total,
+            );
+        } else {
+            println!(
+                "Running {} took {:?}",
+                plural(self.rows.len(), "day"),
+                self.total
+            );
+        }
+        println!("Measured {}", self.setup);
+        if self.rows.iter().any(|row| row.reparsed) {
+            println!("* parses its input again for part two, see `aoc::Challenge::REPARSES`");
+        }
+
+        if self.rows.iter().any(|row| !row.counters.is_empty()) {
+            println!();
+            self.print_counters();
+        }
+        if let Some(calendar) = &self.calendar {
+            println!();
+            self.print_calendar(calendar);
+        }
+    }
+
+    fn print_calendar(&self, calendar: &Calendar) {
+        let total_cpu: Option<Duration> = calendar.days.iter().map(|d| d.cpu).sum();
+        let header = [
+            "day",
+            "wall alone",
+            "CPU alone",
+            "share of CPU",
+            "finished at",
+        ];
+        let mut lines = vec![header.map(String::from)];
+        for (row, day) in self.rows.iter().zip(&calendar.days) {
+            let share = day.cpu.zip(total_cpu).map_or_else(
+                || "n/a".to_owned(),
+                |(cpu, total)| format!("{:.1}%", 100.0 * cpu.as_secs_f64() / total.as_secs_f64()),
+            );
+            lines.push([
+                row.day.clone(),
+                format!("{:?}", day.wall),
+                day.cpu
+                    .map_or_else(|| "n/a".to_owned(), |cpu| format!("{cpu:?}")),
+                share,
+                format!("{:?}", day.finished),
+            ]);
+        }
+
+        let mut widths = [0; 5];
+        for line in &lines {
+            for (width, cell) in widths.iter_mut().zip(line) {
+                *width = (*width).max(cell.len());
+            }
+        }
+        for line in &lines {
+            let cells: Vec<_> = line
+                .iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{cell:width$}"))
+                .collect();
+            println!("{}", cells.join("  ").trim_end());
+        }
+
+        println!(
+            "{} at once on {} took {:?} of wall time, {:?} one after another",
+            plural(self.rows.len(), "day"),
+            plural(calendar.threads, "thread"),
+            calendar.wall,
+            calendar.sequential,
+        );
+        if let Some(cpu) = calendar.cpu {
+            println!(
+                "They used {cpu:?} of CPU time, {:.1}x the wall time (median of {})",
+                cpu.as_secs_f64() / calendar.wall.as_secs_f64(),
+                plural(calendar.runs, "run"),
+            );
+        }
+    }
+
+    fn print_counters(&self) {
+        let count = |c: Option<u64>| c.map_or_else(|| "n/a".to_owned(), |c| c.to_string());
+        let header = [
+            "day",
+            "phase",
+            "instructions",
+            "cycles",
+            "IPC",
+            "branch misses",
+            "cache misses",
+        ];
+        let mut lines = vec![header.map(String::from)];
+        for row in &self.rows {
+            for (phase, counts) in &row.counters {
+                lines.push([
+                    row.day.clone(),
+                    phase.to_string(),
+                    count(counts.instructions),
+                    count(counts.cycles),
+                    counts
+                        .ipc()
+                        .map_or_else(|| "n/a".to_owned(), |ipc| format!("{ipc:.2}")),
+                    count(counts.branch_misses),
+                    count(counts.cache_misses),
+                ]);
+            }
+        }
+
+        let mut widths = [0; 7];
+        for line in &lines {
+            for (width, cell) in widths.iter_mut().zip(line) {
+                *width = (*width).max(cell.len());
+            }
+        }
+        for line in &lines {
+            let cells: Vec<_> = line
+                .iter()
+                .zip(widths)
+                .enumerate()
+                // Right-align the numbers
+                .map(|(i, (cell, width))| match i {
+                    0 | 1 => format!("{cell:width$}"),
+                    _ => format!("{cell:>width$}"),
+                })
+                .collect();
+            println!("{}", cells.join("  ").trim_end());
+        }
+    }
+
+    /// How the `i`th day fared in the concurrent run, if there was one
+    fn share(&self, i: usize) -> Option<DayShare> {
+        self.calendar.as_ref().map(|calendar| calendar.days[i])
+    }
+
+    fn print_json(&self) {
+        let days: Vec<_> = self
+            .rows
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let share = self.share(i);
+                json!({
+                    "day": row.day,
+                    "title": row.title,
+                    "part_one": row.part_one.as_ref().map(|_| answer(&row.part_one)),
+                    "part_two": row.part_two.as_ref().map(|_| answer(&row.part_two)),
+                    "reparsed": row.reparsed,
+                    "first_run_ns": nanos(row.first_run),
+                    "stats": row.stats,
+                    "parallel": share.map(|share| json!({
+                        "wall_ns": nanos(share.wall),
+                        "cpu_ns": share.cpu.map(nanos),
+                        "finished_ns": nanos(share.finished),
+                    })),
+                    "counters": row
+                        .counters
+                        .iter()
+                        .map(|(phase, counts)| json!({ "phase": phase.to_string(), "counts": counts }))
+                        .collect::<Vec<_>>(),
+                    "baseline_mean_ns": row.change.map(|c| nanos(c.baseline)),
+                    "change": row.change.map(|c| c.ratio),
+                    "verdict": row.change.map(|c| c.verdict.to_string()),
+                })
+            })
+            .collect();
+        let report = json!({
+            "days": days,
+            "total_ns": nanos(self.total),
+            "setup": self.setup,
+            "parallel": self.calendar.as_ref().map(|calendar| json!({
+                "threads": calendar.threads,
+                "runs": calendar.runs,
+                "wall_ns": nanos(calendar.wall),
+                "cpu_ns": calendar.cpu.map(nanos),
+                "sequential_ns": nanos(calendar.sequential),
+            })),
+        });
+        println!("{report:#}");
+    }
+
+    fn print_csv(&self) {
+        println!(
+            "day,title,part_one,part_two,reparsed,first_run_ns,\
+             samples,outliers,sample_size,median_ns,mean_ns,p95_ns,min_ns,max_ns,stddev_ns,\
+             baseline_mean_ns,change,verdict,\
+             instructions,cycles,branch_misses,cache_misses,\
+             alone_cpu_ns,parallel_finished_ns,setup"
+        );
+        for (i, row) in self.rows.iter().enumerate() {
+            let share = self.share(i);
+            let stats = row.stats.map_or_else(
+                || ",".repeat(8),
+                |s| {
+                    format!(
+                        "{},{},{},{},{},{},{},{},{}",
+                        s.samples,
+                        s.outliers,
+                        s.sample_size,
+                        s.median_ns,
+                        s.mean_ns,
+                        s.p95_ns,
+                        s.min_ns,
+                        s.max_ns,
+                        s.stddev_ns,
+                    )
+                },
+            );
+            let count = |c: Option<u64>| c.map_or_else(String::new, |c| c.to_string());
+            let counts = Counts::total(&row.counters).unwrap_or_default();
+            println!(
+                "{},{},{},{},{},{},{stats},{},{},{},{},{},{},{},{},{},{}",
+                row.day,
+                csv_field(row.title),
+                csv_field(&answer(&row.part_one)),
+                csv_field(&answer(&row.part_two)),
+                row.reparsed,
+                nanos(row.first_run),
+                row.change
+                    .map_or_else(String::new, |c| nanos(c.baseline).to_string()),
+                row.change.map_or_else(String::new, |c| c.ratio.to_string()),
+                row.change
+                    .map_or_else(String::new, |c| c.verdict.to_string()),
+                count(counts.instructions),
+                count(counts.cycles),
+                count(counts.branch_misses),
+                count(counts.cache_misses),
+                count(share.and_then(|s| s.cpu).map(nanos)),
+                count(share.map(|s| nanos(s.finished))),
+                csv_field(&self.setup.to_string()),
+            );
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use std::{fmt, fs, hint::black_box, io};
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// Whether the caches are cleared before each sample.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Cache {
+    /// Leave the caches alone, so samples after the first find the input already cached
+    #[default]
+    Warm,
+    /// Evict the caches before each sample, which then runs the day only once
+    Cold,
+}
+
+/// A list of core numbers
+pub type Cores = Vec<usize>;
+
+/// The conditions a run was measured under, so that runs can be told apart.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Setup {
+    /// The cores the runner was pinned to, if any
+    pub cores: Option<Cores>,
+    /// How many threads rayon was limited to, if any
+    pub threads: Option<usize>,
+    pub cache: Cache,
+}
+
+impl fmt::Display for Setup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.cores {
+            Some(cores) => write!(f, "pinned to cores {}", format_cores(cores))?,
+            None => f.write_str("not pinned")?,
+        }
+        match self.threads {
+            Some(1) => f.write_str(", single-threaded")?,
+            Some(n) => write!(f, ", {n} threads")?,
+            None => {}
+        }
+        match self.cache {
+            Cache::Warm => f.write_str(", warm cache"),
+            Cache::Cold => f.write_str(", cold cache"),
+        }
+    }
+}
+
+impl Setup {
+    /// Pins the process and sizes rayon's pool.
+    /// Has to happen before rayon starts its threads, so that they inherit the pinning.
+    pub fn apply(&self) -> io::Result<()> {
+        if let Some(cores) = &self.cores {
+            pin(cores).map_err(|err| {
+                let cores = format_cores(cores);
+                io::Error::new(err.kind(), format!("could not pin to cores {cores}: {err}"))
+            })?;
+        }
+        if let Some(threads) = self.threads {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build_global()
+                .map_err(io::Error::other)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses a list of cores like `0,2-3`.
+pub fn parse_cores(s: &str) -> Result<Cores, String> {
+    let mut cores = Vec::new();
+    for part in s.split(',') {
+        let parse = |n: &str| {
+            n.trim()
+                .parse::<usize>()
+                .map_err(|err| format!("{n:?} is not a core number: {err}"))
+        };
+        match part.split_once('-') {
+            Some((start, end)) => cores.extend(parse(start)?..=parse(end)?),
+            None => cores.push(parse(part)?),
+        }
+    }
+    cores.sort_unstable();
+    cores.dedup();
+    Ok(cores)
+}
+
+/// Formats cores back into a list like `0,2-3`
+fn format_cores(cores: &[usize]) -> String {
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for &core in cores {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == core => *end = core,
+            _ => ranges.push((core, core)),
+        }
+    }
+    let ranges: Vec<_> = ranges
+        .into_iter()
+        .map(|(start, end)| {
+            if start == end {
+                start.to_string()
+            } else {
+                format!("{start}-{end}")
+            }
+        })
+        .collect();
+    ranges.join(",")
+}
+
+#[cfg(target_os = "linux")]
+fn pin(cores: &[usize]) -> io::Result<()> {
+    // SAFETY: cpu_set_t is plain data, for which all zeroes is the empty set
+    let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    for &core in cores {
+        if core >= libc::CPU_SETSIZE as usize {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("core {core} is out of range"),
+            ));
+        }
+        // SAFETY: the core was just checked to fit in the set
+        unsafe { libc::CPU_SET(core, &mut set) };
+    }
+    // SAFETY: set is a valid cpu_set_t of the size given, and pid 0 means this thread
+    let result = unsafe { libc::sched_setaffinity(0, std::mem::size_of_val(&set), &set) };
+    match result {
+        0 => Ok(()),
+        _ => Err(io::Error::last_os_error()),
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn pin(_: &[usize]) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "pinning to cores is only supported on Linux",
+    ))
+}
+
+/// A buffer bigger than the caches, which pushes everything else out when it's written to.
+pub struct Evictor {
+    buffer: Vec<u8>,
+}
+
+impl Evictor {
+    /// Sizes the buffer to twice the largest cache, or `size` bytes if given.
+    pub fn new(size: Option<usize>) -> Self {
+        let size = size.unwrap_or_else(|| 2 * largest_cache().unwrap_or(32 << 20));
+        Evictor {
+            buffer: vec![0; size],
+        }
+    }
+
+    pub fn evict(&mut self) {
+        // Touching one byte per line is enough to pull each line in
+        for line in self.buffer.chunks_mut(64) {
+            line[0] = line[0].wrapping_add(1);
+        }
+        black_box(&mut self.buffer);
+    }
+}
+
+/// The size of the biggest cache on the first core, according to sysfs
+fn largest_cache() -> Option<usize> {
+    let caches = fs::read_dir("/sys/devices/system/cpu/cpu0/cache").ok()?;
+    caches
+        .flatten()
+        .filter_map(|cache| {
+            let size = fs::read_to_string(cache.path().join("size")).ok()?;
+            let size = size.trim();
+            let (n, unit) = size.split_at(size.find(|c: char| !c.is_ascii_digit())?);
+            let n: usize = n.parse().ok()?;
+            match unit {
+                "K" => Some(n << 10),
+                "M" => Some(n << 20),
+                _ => None,
+            }
+        })
+        .max()
+}
@@ -9,11 +9,11 @@ impl<'a> aoc::Parser<'a> for Solution<'a> {
 }
 
 impl Solution<'_> {
-    fn part_one(self) -> impl std::fmt::Display {
+    fn part_one(self) -> impl aoc::IntoAnswer {
         0
     }
 
-    fn part_two(self) -> impl std::fmt::Display {
+    fn part_two(self) -> impl aoc::IntoAnswer {
         0
     }
 }
@@ -24,19 +24,21 @@ impl Solution<'_> {
 // }
 
 impl aoc::Challenge for Solution<'_> {
-    fn part_one(self) -> impl std::fmt::Display {
+    fn part_one(self) -> impl aoc::IntoAnswer {
         self.part_one()
     }
 
-    fn part_two(self) -> impl std::fmt::Display {
+    fn part_two(self) -> impl aoc::IntoAnswer {
         self.part_two()
     }
 }
 
+aoc::solution!(Solution<'static>, year = 2023, day = 0, title = "");
+
 #[cfg(test)]
 mod tests {
     use super::Solution;
-    use aoc::Parser;
+    use aoc::{Answer, IntoAnswer, Parser};
 
     const INPUT: &str = "";
 
@@ -49,12 +51,12 @@ mod tests {
     #[test]
     fn part_one() {
         let output = Solution::must_parse(INPUT);
-        assert_eq!(output.part_one().to_string(), "0");
+        assert_eq!(output.part_one().into_answer(), Answer::Int(0));
     }
 
     #[test]
     fn part_two() {
         let output = Solution::must_parse(INPUT);
-        assert_eq!(output.part_two().to_string(), "0");
+        assert_eq!(output.part_two().into_answer(), Answer::Int(0));
     }
 }
@@ -0,0 +1,82 @@
+//! Lines of digits, spelled out digits and filler letters that can't spell one, each with
+//! a plain digit somewhere so that part one has a value for every line.
+
+use proptest::{collection::vec, prelude::*, sample::Index};
+
+use crate::Solution;
+
+const WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+#[derive(Debug, Clone, Copy)]
+enum Token {
+    Filler(char),
+    Digit(u8),
+    Word(u8),
+}
+
+fn token() -> impl Strategy<Value = Token> {
+    prop_oneof![
+        // none of these letters are in a digit's name
+        prop::sample::select(b"abcdjklmpqyz".to_vec()).prop_map(|b| Token::Filler(b as char)),
+        (1..=9u8).prop_map(Token::Digit),
+        (1..=9u8).prop_map(Token::Word),
+    ]
+}
+
+fn line() -> impl Strategy<Value = Vec<Token>> {
+    (vec(token(), 0..12), 1..=9u8, any::<Index>()).prop_map(|(mut tokens, digit, at)| {
+        tokens.insert(at.index(tokens.len() + 1), Token::Digit(digit));
+        tokens
+    })
+}
+
+fn render(lines: &[Vec<Token>]) -> String {
+    let mut input = String::new();
+    for line in lines {
+        for &token in line {
+            match token {
+                Token::Filler(c) => input.push(c),
+                Token::Digit(d) => input.push((b'0' + d) as char),
+                Token::Word(d) => input += WORDS[d as usize - 1],
+            }
+        }
+        input.push('\n');
+    }
+    input
+}
+
+/// `lines` with each token passed through `f`
+fn map(lines: &[Vec<Token>], f: impl Fn(Token) -> Token) -> Vec<Vec<Token>> {
+    lines
+        .iter()
+        .map(|line| line.iter().copied().map(&f).collect())
+        .collect()
+}
+
+proptest! {
+    /// Part two reads a spelled out digit the same as the digit itself
+    #[test]
+    fn spelling_out_keeps_part_two(lines in vec(line(), 1..20)) {
+        let spelled = map(&lines, |token| match token {
+            Token::Digit(d) => Token::Word(d),
+            token => token,
+        });
+        prop_assert_eq!(
+            aoc::run_part_two::<Solution>(&render(&spelled)).unwrap(),
+            aoc::run_part_two::<Solution>(&render(&lines)).unwrap()
+        );
+    }
+
+    /// With nothing spelled out, both parts find the same digits
+    #[test]
+    fn plain_digits_agree(lines in vec(line(), 1..20)) {
+        let plain = map(&lines, |token| match token {
+            Token::Word(d) => Token::Digit(d),
+            token => token,
+        });
+        let run = aoc::run::<Solution>(&render(&plain)).unwrap();
+        prop_assert_eq!(run.part_one, run.part_two);
+    }
+}
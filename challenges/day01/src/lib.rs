@@ -1,6 +1,7 @@
-use std::fmt::Display;
+use aoc::{Challenge, IntoAnswer, Phase};
 
-use aoc::Challenge;
+#[cfg(test)]
+mod generate;
 
 #[allow(clippy::upper_case_acronyms)]
 #[repr(u8)]
@@ -156,7 +157,10 @@ const STATE: [u16; 1024] = {
 };
 
 #[derive(Debug, PartialEq, Copy, Clone)]
-pub struct Solution {
+pub struct Solution<'a>(&'a str);
+
+#[derive(Debug, PartialEq, Copy, Clone, Default)]
+struct Answers {
     part_one: u32,
     part_two: u32,
 }
@@ -169,17 +173,21 @@ struct LineSolution {
     last: u8,
 }
 
-impl<'a> aoc::Parser<'a> for Solution {
+impl<'a> aoc::Parser<'a> for Solution<'a> {
     fn parse(input: &'a str) -> nom::IResult<&'a str, Self> {
-        let mut output = Solution {
-            part_one: 0,
-            part_two: 0,
-        };
+        Ok(("", Solution(input)))
+    }
+}
+
+impl Solution<'_> {
+    /// Both parts look for the same digits, so they share one pass over the input
+    fn solve(self) -> Answers {
+        let mut output = Answers::default();
         let mut sol = LineSolution::default();
 
         let mut state = 0;
 
-        for b in input.bytes() {
+        for b in self.0.bytes() {
             let j = (state & 0x03e0) | (CHARS[b as usize] as u16 & 0x1f);
             state = STATE[j as usize];
             match state & 0x1f {
@@ -212,32 +220,46 @@ impl<'a> aoc::Parser<'a> for Solution {
             output.part_two += (x.first * 10 + x.last) as u32;
         }
 
-        Ok(("", output))
+        output
     }
 }
 
-impl Challenge for Solution {
-    fn part_one(self) -> impl Display {
-        self.part_one
+impl Challenge for Solution<'_> {
+    const ALLOC_FREE: &'static [Phase] =
+        &[Phase::Parse, Phase::PartOne, Phase::PartTwo, Phase::Both];
+    // `solve_both` always solves both parts from the one parse
+    const REPARSES: bool = false;
+
+    fn part_one(self) -> impl IntoAnswer {
+        self.solve().part_one
+    }
+
+    fn part_two(self) -> impl IntoAnswer {
+        self.solve().part_two
     }
 
-    fn part_two(self) -> impl Display {
-        self.part_two
+    fn solve_both(self) -> Result<aoc::Run, Self> {
+        let Answers { part_one, part_two } = self.solve();
+        Ok(aoc::Run {
+            part_one: part_one.into_answer(),
+            part_two: part_two.into_answer(),
+        })
     }
 }
 
+aoc::solution!(
+    Solution<'static>,
+    year = 2023,
+    day = 1,
+    title = "Trebuchet?!"
+);
+aoc::example_tests!(Solution<'static>);
+
 #[cfg(test)]
 mod tests {
     use super::Solution;
     use aoc::{Challenge, Parser};
 
-    const INPUT: &str = "1abc2
-pqr3stu8vwx
-a1b2c3d4e5f
-treb7uchet
-
-";
-
     const INPUT2: &str = "two1nine
 eightwothree
 abcone2threexyz
@@ -249,20 +271,9 @@ zoneight234
 ";
 
     #[test]
-    fn parse() {
-        let output = Solution::parse(INPUT2).unwrap().1;
-        println!("{output:?}");
-    }
-
-    #[test]
-    fn part_one() {
-        let output = Solution::parse(INPUT).unwrap().1;
-        assert_eq!(output.part_one().to_string(), "142");
-    }
-
-    #[test]
-    fn part_two() {
+    fn solve_both() {
         let output = Solution::parse(INPUT2).unwrap().1;
-        assert_eq!(output.part_two().to_string(), "281");
+        let run = output.solve_both().unwrap();
+        assert_eq!(run.part_two.to_string(), "281");
     }
 }
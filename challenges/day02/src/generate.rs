@@ -0,0 +1,69 @@
+//! Games numbered from 1 in order, as the solution counts them rather than reading the ids,
+//! each round showing any colour at most once.
+
+use proptest::{collection::vec, prelude::*};
+
+use crate::Solution;
+
+const COLOURS: [&str; 3] = ["red", "green", "blue"];
+
+/// The cubes of each colour shown, in the order they're listed
+type Round = Vec<(usize, u8)>;
+
+fn round() -> impl Strategy<Value = Round> {
+    prop::sample::subsequence(vec![0, 1, 2], 1..=3)
+        .prop_flat_map(|colours| {
+            let counts = vec(1..=20u8, colours.len());
+            (Just(colours), counts)
+        })
+        .prop_map(|(colours, counts)| colours.into_iter().zip(counts).collect::<Round>())
+        .prop_shuffle()
+}
+
+fn games() -> impl Strategy<Value = Vec<Vec<Round>>> {
+    vec(vec(round(), 1..6), 1..20)
+}
+
+fn render(games: &[Vec<Round>]) -> String {
+    let mut input = String::new();
+    for (id, game) in games.iter().enumerate() {
+        let rounds: Vec<String> = game
+            .iter()
+            .map(|round| {
+                let cubes: Vec<String> = round
+                    .iter()
+                    .map(|&(colour, count)| format!("{count} {}", COLOURS[colour]))
+                    .collect();
+                cubes.join(", ")
+            })
+            .collect();
+        input += &format!("Game {}: {}\n", id + 1, rounds.join("; "));
+    }
+    input
+}
+
+proptest! {
+    /// A game is the same whichever order its rounds and cubes are listed in
+    #[test]
+    fn reordering_rounds_keeps_the_games(games in games()) {
+        let run = aoc::run::<Solution>(&render(&games)).unwrap();
+        let mut reordered = games;
+        for game in &mut reordered {
+            game.reverse();
+            for round in game {
+                round.sort();
+            }
+        }
+        prop_assert_eq!(aoc::run::<Solution>(&render(&reordered)).unwrap(), run);
+    }
+
+    /// A game with too many red cubes isn't possible, and without green or blue cubes its
+    /// power is zero, so it adds to neither part
+    #[test]
+    fn an_impossible_game_adds_nothing(games in games(), red in 13..=20u8) {
+        let run = aoc::run::<Solution>(&render(&games)).unwrap();
+        let mut more = games;
+        more.push(vec![vec![(0, red)]]);
+        prop_assert_eq!(aoc::run::<Solution>(&render(&more)).unwrap(), run);
+    }
+}
@@ -1,6 +1,4 @@
-use std::fmt::Display;
-
-use aoc::Challenge;
+use aoc::{Challenge, IntoAnswer};
 use nom::{
     branch::alt, bytes::complete::tag, character::complete::digit1, sequence::tuple, IResult,
     Parser,
@@ -8,6 +6,9 @@ use nom::{
 use nom_supreme::ParserExt;
 use parsers::{number, ParserExt2 as _};
 
+#[cfg(test)]
+mod generate;
+
 enum Colour {
     Red(u8),
     Green(u8),
@@ -123,42 +124,14 @@ impl Game {
 }
 
 impl Challenge for Solution {
-    fn part_one(self) -> impl Display {
+    fn part_one(self) -> impl IntoAnswer {
         self.part_one
     }
 
-    fn part_two(self) -> impl Display {
+    fn part_two(self) -> impl IntoAnswer {
         self.part_two
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::Solution;
-    use aoc::{Challenge, Parser};
-
-    const INPUT: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
-Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
-Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
-";
-
-    #[test]
-    fn parse() {
-        let output = Solution::parse(INPUT).unwrap().1;
-        println!("{output:?}");
-    }
-
-    #[test]
-    fn part_one() {
-        let output = Solution::parse(INPUT).unwrap().1;
-        assert_eq!(output.part_one().to_string(), "8");
-    }
-
-    #[test]
-    fn part_two() {
-        let output = Solution::parse(INPUT).unwrap().1;
-        assert_eq!(output.part_two().to_string(), "2286");
-    }
-}
+aoc::solution!(Solution, year = 2023, day = 2, title = "Cube Conundrum");
+aoc::example_tests!(Solution);
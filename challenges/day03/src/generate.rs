@@ -0,0 +1,75 @@
+//! Schematics of numbers up to three digits without leading zeros, the most part two has
+//! room for, among symbols and dots.
+
+use proptest::{collection::vec, prelude::*};
+
+use crate::Solution;
+
+type Schematic = Vec<Vec<u8>>;
+
+fn cell() -> impl Strategy<Value = u8> {
+    prop_oneof![
+        5 => Just(b'.'),
+        4 => prop::sample::select(b"0123456789".to_vec()),
+        2 => prop::sample::select(b"*#$%&+-/=@".to_vec()),
+    ]
+}
+
+fn schematic() -> impl Strategy<Value = Schematic> {
+    (1..=15usize, 1..=15usize)
+        .prop_flat_map(|(width, height)| vec(vec(cell(), width), height))
+        .prop_map(|mut rows| {
+            for row in &mut rows {
+                let mut digits = 0;
+                for cell in row.iter_mut() {
+                    if !cell.is_ascii_digit() {
+                        digits = 0;
+                    } else if digits == 3 {
+                        *cell = b'.';
+                        digits = 0;
+                    } else {
+                        if digits == 0 && *cell == b'0' {
+                            *cell = b'1';
+                        }
+                        digits += 1;
+                    }
+                }
+            }
+            rows
+        })
+}
+
+fn render(schematic: &Schematic) -> String {
+    let mut input = String::new();
+    for row in schematic {
+        input.extend(row.iter().map(|&c| c as char));
+        input.push('\n');
+    }
+    input
+}
+
+proptest! {
+    /// Being next to a symbol doesn't care which way up the schematic is
+    #[test]
+    fn flipping_keeps_the_parts(schematic in schematic()) {
+        let run = aoc::run::<Solution>(&render(&schematic)).unwrap();
+        let mut flipped = schematic;
+        flipped.reverse();
+        prop_assert_eq!(aoc::run::<Solution>(&render(&flipped)).unwrap(), run);
+    }
+
+    /// Any symbol makes a part number, but only a `*` is a gear
+    #[test]
+    fn only_stars_are_gears(schematic in schematic()) {
+        let run = aoc::run::<Solution>(&render(&schematic)).unwrap();
+        let mut hashes = schematic;
+        for cell in hashes.iter_mut().flatten() {
+            if *cell == b'*' {
+                *cell = b'#';
+            }
+        }
+        let hashed = aoc::run::<Solution>(&render(&hashes)).unwrap();
+        prop_assert_eq!(hashed.part_one, run.part_one);
+        prop_assert_eq!(hashed.part_two, aoc::Answer::Int(0));
+    }
+}
@@ -1,7 +1,9 @@
-use std::fmt::Display;
-
-use aoc::Challenge;
+use aoc::{safe, Challenge, IntoAnswer};
 use bitvec::{bitvec, vec::BitVec};
+use nom::error::ErrorKind;
+
+#[cfg(test)]
+mod generate;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Solution {
@@ -13,16 +15,24 @@ pub struct Solution {
 
 impl<'a> aoc::Parser<'a> for Solution {
     fn parse(input: &'a str) -> nom::IResult<&'a str, Self> {
-        let width = input.find('\n').unwrap();
+        let Some(width) = input.find('\n') else {
+            return Err(safe::error_at(input, input.len(), ErrorKind::CrLf));
+        };
         let height = input.len() / (width + 1);
 
         let mut nums = vec![255; (width + 2) * (height + 2)];
         let mut gears = bitvec![0; (width+2) * (height + 2)];
         let mut symbols = bitvec![0; (width+2) * (height + 2)];
         let mut pos = width + 3;
-        for chunk in input.as_bytes().chunks_exact(width + 1) {
+        for (row, chunk) in input.as_bytes().chunks_exact(width + 1).enumerate() {
+            let start = row * (width + 1);
+            safe::ensure_at(chunk[width] == b'\n', input, start + width, ErrorKind::CrLf)?;
             let chunk = &chunk[..width];
-            for b in chunk {
+            // part two only has room for the gears around numbers of up to three digits
+            let mut digits = 0;
+            for (col, b) in chunk.iter().enumerate() {
+                digits = if b.is_ascii_digit() { digits + 1 } else { 0 };
+                safe::ensure_at(digits <= 3, input, start + col, ErrorKind::TooLarge)?;
                 if b.is_ascii_digit() {
                     nums[pos] = *b - b'0';
                 } else if *b == b'*' {
@@ -35,6 +45,8 @@ impl<'a> aoc::Parser<'a> for Solution {
             }
             pos += 2;
         }
+        let end = height * (width + 1);
+        safe::ensure_at(end == input.len(), input, end, ErrorKind::LengthValue)?;
 
         Ok((
             "",
@@ -49,7 +61,7 @@ impl<'a> aoc::Parser<'a> for Solution {
 }
 
 impl Challenge for Solution {
-    fn part_one(self) -> impl Display {
+    fn part_one(self) -> impl IntoAnswer {
         let mut sum = 0;
         let mut current_num = 0;
         let mut is_next_to = false;
@@ -78,7 +90,7 @@ impl Challenge for Solution {
         sum
     }
 
-    fn part_two(self) -> impl Display {
+    fn part_two(self) -> impl IntoAnswer {
         // no part numbers are > 999
         let mut spots = bitvec![0; 15]; // 3 * (3+2);
         let mut gears: Vec<GearRatio> = vec![GearRatio::One(0); self.gears.len()];
@@ -206,38 +218,5 @@ impl Solution {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::Solution;
-    use aoc::{Challenge, Parser};
-
-    const INPUT: &str = "467..114..
-...*......
-..35..633.
-......#...
-617*......
-.....+.58.
-..592.....
-......755.
-...$.*....
-.664.598..
-";
-
-    #[test]
-    fn parse() {
-        let output = Solution::parse(INPUT).unwrap().1;
-        println!("{output:?}");
-    }
-
-    #[test]
-    fn part_one() {
-        let output = Solution::parse(INPUT).unwrap().1;
-        assert_eq!(output.part_one().to_string(), "4361");
-    }
-
-    #[test]
-    fn part_two() {
-        let output = Solution::parse(INPUT).unwrap().1;
-        assert_eq!(output.part_two().to_string(), "467835");
-    }
-}
+aoc::solution!(Solution, year = 2023, day = 3, title = "Gear Ratios");
+aoc::example_tests!(Solution);
@@ -0,0 +1,84 @@
+//! Cards laid out like the real input's, ten winning numbers and twenty five held, with no
+//! number twice on the same side and no card winning copies of cards past the end.
+
+use proptest::{collection::vec, prelude::*};
+
+use crate::Solution;
+
+/// The winning numbers and the ones held
+type Card = (Vec<u8>, Vec<u8>);
+
+fn side(len: usize) -> impl Strategy<Value = Vec<u8>> {
+    prop::sample::subsequence((1..100).collect::<Vec<u8>>(), len).prop_shuffle()
+}
+
+fn cards() -> impl Strategy<Value = Vec<Card>> {
+    vec((side(10), side(25)), 1..30).prop_map(|mut cards| {
+        let len = cards.len();
+        for (i, (winning, held)) in cards.iter_mut().enumerate() {
+            // swap out matches until the copies won fit in the cards after this one
+            let spare: Vec<u8> = (1..100)
+                .filter(|n| !winning.contains(n) && !held.contains(n))
+                .collect();
+            let mut spare = spare.into_iter();
+            let mut matches = held.iter().filter(|n| winning.contains(n)).count();
+            for n in held.iter_mut() {
+                if matches < len - i {
+                    break;
+                }
+                if winning.contains(n) {
+                    *n = spare.next().unwrap();
+                    matches -= 1;
+                }
+            }
+        }
+        cards
+    })
+}
+
+fn render(cards: &[Card]) -> String {
+    let numbers = |side: &[u8]| -> String {
+        let numbers: Vec<String> = side.iter().map(|n| format!("{n:>2}")).collect();
+        numbers.join(" ")
+    };
+    cards
+        .iter()
+        .enumerate()
+        .map(|(i, (winning, held))| {
+            format!(
+                "Card {:>3}: {} | {}\n",
+                i + 1,
+                numbers(winning),
+                numbers(held)
+            )
+        })
+        .collect()
+}
+
+proptest! {
+    /// Only which numbers match counts, not where they are on the card
+    #[test]
+    fn sorting_keeps_the_points(cards in cards()) {
+        let run = aoc::run::<Solution>(&render(&cards)).unwrap();
+        let mut sorted = cards;
+        for (winning, held) in &mut sorted {
+            winning.sort();
+            held.sort();
+        }
+        prop_assert_eq!(aoc::run::<Solution>(&render(&sorted)).unwrap(), run);
+    }
+
+    /// A last card without a match scores nothing and wins no copies, so it's only itself
+    #[test]
+    fn a_losing_card_is_one_more_card(cards in cards()) {
+        let run = aoc::run::<Solution>(&render(&cards)).unwrap();
+        let mut more = cards;
+        more.push(((1..=10).collect(), (11..=35).collect()));
+        let more = aoc::run::<Solution>(&render(&more)).unwrap();
+        prop_assert_eq!(more.part_one, run.part_one);
+        let aoc::Answer::Int(won) = run.part_two else {
+            panic!("part two isn't a number");
+        };
+        prop_assert_eq!(more.part_two, aoc::Answer::Int(won + 1));
+    }
+}
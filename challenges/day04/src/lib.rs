@@ -1,8 +1,12 @@
 #![feature(vec_push_within_capacity)]
 
-use std::fmt::{Debug, Display};
+use std::fmt::Debug;
 
-use aoc::Challenge;
+use aoc::{safe, Challenge, IntoAnswer};
+use nom::error::ErrorKind;
+
+#[cfg(test)]
+mod generate;
 
 #[derive(bytemuck::Pod, bytemuck::Zeroable, Clone, Copy)]
 #[repr(C, align(1))]
@@ -12,6 +16,13 @@ impl Triple {
     fn into_u8(self) -> u8 {
         (self.0 & 0xf) * 10 + (self.1 & 0xf)
     }
+
+    /// Whether this is a number of one or two digits, padded to three bytes
+    fn is_valid(&self) -> bool {
+        matches!(self.0, b' ' | b'0'..=b'9')
+            && self.1.is_ascii_digit()
+            && matches!(self.2, b' ' | b'\n')
+    }
 }
 
 impl Debug for Triple {
@@ -31,43 +42,74 @@ pub struct Solution(Vec<u8>);
 
 impl<'a> aoc::Parser<'a> for Solution {
     fn parse(input: &'a str) -> nom::IResult<&'a str, Self> {
-        let colon = input.find(':').unwrap();
-        let bar = input.find('|').unwrap();
-        let nl = input.find('\n').unwrap();
+        let missing = |kind| safe::error_at(input, input.len(), kind);
+        let colon = input.find(':').ok_or_else(|| missing(ErrorKind::Char))?;
+        let bar = input.find('|').ok_or_else(|| missing(ErrorKind::Char))?;
+        let nl = input.find('\n').ok_or_else(|| missing(ErrorKind::CrLf))?;
 
         let mut output = Vec::with_capacity(input.len() / (nl + 1));
 
         if colon == 8 {
-            for line in input.as_bytes().chunks_exact(nl + 1) {
-                let winning = &line[colon + 2..bar];
-                let holding = &line[bar + 2..nl + 1];
-
-                let card = Card::<10, 25> {
-                    winning: bytemuck::cast_slice(winning).try_into().unwrap(),
-                    holding: bytemuck::cast_slice(holding).try_into().unwrap(),
-                };
+            for (i, line) in input.as_bytes().chunks_exact(nl + 1).enumerate() {
+                let start = i * (nl + 1);
+                let card = Card::<10, 25>::new(line, colon, bar)
+                    .ok_or_else(|| safe::error_at(input, start, ErrorKind::Verify))?;
+                if let Some(at) = card.invalid(colon, bar) {
+                    return Err(safe::error_at(input, start + at, ErrorKind::Digit));
+                }
 
                 output.push_within_capacity(card.count() as u8).unwrap();
             }
         } else {
-            for line in input.as_bytes().chunks_exact(nl + 1) {
-                let winning = &line[colon + 2..bar];
-                let holding = &line[bar + 2..nl + 1];
-
-                let card = Card::<5, 8> {
-                    winning: bytemuck::cast_slice(winning).try_into().unwrap(),
-                    holding: bytemuck::cast_slice(holding).try_into().unwrap(),
-                };
+            for (i, line) in input.as_bytes().chunks_exact(nl + 1).enumerate() {
+                let start = i * (nl + 1);
+                let card = Card::<5, 8>::new(line, colon, bar)
+                    .ok_or_else(|| safe::error_at(input, start, ErrorKind::Verify))?;
+                if let Some(at) = card.invalid(colon, bar) {
+                    return Err(safe::error_at(input, start + at, ErrorKind::Digit));
+                }
 
                 output.push_within_capacity(card.count() as u8).unwrap();
             }
         }
 
+        let end = output.len() * (nl + 1);
+        safe::ensure_at(end == input.len(), input, end, ErrorKind::LengthValue)?;
+
         Ok(("", Self(output)))
     }
 }
 
-impl<const W: usize, const H: usize> Card<'_, W, H> {
+impl<'a, const W: usize, const H: usize> Card<'a, W, H> {
+    /// Reads the card on a line, with the `:` and `|` where they were on the first line
+    fn new(line: &'a [u8], colon: usize, bar: usize) -> Option<Self> {
+        let card = Self {
+            winning: bytemuck::try_cast_slice(line.get(colon + 2..bar)?)
+                .ok()?
+                .try_into()
+                .ok()?,
+            holding: bytemuck::try_cast_slice(line.get(bar + 2..)?)
+                .ok()?
+                .try_into()
+                .ok()?,
+        };
+        Some(card)
+    }
+
+    /// Where the first number on the line that isn't one or two digits starts, as `count`
+    /// indexes by the numbers. Only checked when the `safe` feature is on.
+    fn invalid(&self, colon: usize, bar: usize) -> Option<usize> {
+        if !safe::ENABLED {
+            return None;
+        }
+        let at = |start: usize, i: usize| start + 2 + 3 * i;
+        let winning = self.winning.iter().position(|t| !t.is_valid());
+        let holding = || self.holding.iter().position(|t| !t.is_valid());
+        winning
+            .map(|i| at(colon, i))
+            .or_else(|| holding().map(|i| at(bar, i)))
+    }
+
     fn count(self) -> usize {
         // two digits can only go up to 100
         let mut bv = [false; 100];
@@ -83,11 +125,11 @@ impl<const W: usize, const H: usize> Card<'_, W, H> {
 }
 
 impl Challenge for Solution {
-    fn part_one(self) -> impl Display {
+    fn part_one(self) -> impl IntoAnswer {
         self.0.into_iter().map(|len| (1 << len) >> 1).sum::<usize>()
     }
 
-    fn part_two(self) -> impl Display {
+    fn part_two(self) -> impl IntoAnswer {
         let mut score = 0u32;
         let mut current = 1u32;
         let mut changes = vec![0u32; self.0.len() + 10];
@@ -102,34 +144,5 @@ impl Challenge for Solution {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::Solution;
-    use aoc::{Challenge, Parser};
-
-    const INPUT: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
-Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
-Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
-Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
-Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
-Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
-";
-
-    #[test]
-    fn parse() {
-        let output = Solution::parse(INPUT).unwrap().1;
-        println!("{output:?}");
-    }
-
-    #[test]
-    fn part_one() {
-        let output = Solution::parse(INPUT).unwrap().1;
-        assert_eq!(output.part_one().to_string(), "13");
-    }
-
-    #[test]
-    fn part_two() {
-        let output = Solution::parse(INPUT).unwrap().1;
-        assert_eq!(output.part_two().to_string(), "30");
-    }
-}
+aoc::solution!(Solution, year = 2023, day = 4, title = "Scratchcards");
+aoc::example_tests!(Solution);
@@ -0,0 +1,86 @@
+//! Almanacs of seven maps, each sending ranges of sources that don't overlap, as the
+//! solution keeps one destination per source.
+
+use proptest::{collection::vec, prelude::*};
+
+use crate::Solution;
+
+const NAMES: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+
+/// Ranges as destination, source and length
+type Map = Vec<(u32, u32, u32)>;
+
+fn map() -> impl Strategy<Value = Map> {
+    (1..=5usize)
+        .prop_flat_map(|ranges| {
+            (
+                prop::sample::subsequence((0..200).collect::<Vec<u32>>(), 2 * ranges),
+                vec(0..200u32, ranges),
+            )
+        })
+        .prop_map(|(bounds, dsts)| {
+            let sources = bounds
+                .chunks_exact(2)
+                .map(|pair| (pair[0], pair[1] - pair[0]));
+            dsts.into_iter()
+                .zip(sources)
+                .map(|(dst, (src, len))| (dst, src, len))
+                .collect::<Map>()
+        })
+        .prop_shuffle()
+}
+
+fn maps() -> impl Strategy<Value = Vec<Map>> {
+    vec(map(), 7)
+}
+
+/// Seed ranges as start and length
+fn seeds() -> impl Strategy<Value = Vec<(u32, u32)>> {
+    vec((0..250u32, 1..50u32), 1..5)
+}
+
+fn render(seeds: &[u32], maps: &[Map]) -> String {
+    let seeds: Vec<String> = seeds.iter().map(u32::to_string).collect();
+    let maps: Vec<String> = maps
+        .iter()
+        .zip(NAMES)
+        .map(|(map, name)| {
+            let ranges: String = map
+                .iter()
+                .map(|(dst, src, len)| format!("{dst} {src} {len}\n"))
+                .collect();
+            format!("{name} map:\n{ranges}")
+        })
+        .collect();
+    format!("seeds: {}\n\n{}", seeds.join(" "), maps.join("\n"))
+}
+
+proptest! {
+    /// Ranges of one seed each are just those seeds, twice over for part one
+    #[test]
+    fn single_seeds_are_part_one(seeds in seeds(), maps in maps()) {
+        let starts: Vec<u32> = seeds.iter().flat_map(|&(start, _)| [start, start]).collect();
+        let singles: Vec<u32> = seeds.iter().flat_map(|&(start, _)| [start, 1]).collect();
+        prop_assert_eq!(
+            aoc::run_part_two::<Solution>(&render(&singles, &maps)).unwrap(),
+            aoc::run_part_one::<Solution>(&render(&starts, &maps)).unwrap()
+        );
+    }
+
+    /// A map is the same whichever order its ranges are listed in
+    #[test]
+    fn reordering_ranges_keeps_the_maps(seeds in seeds(), mut maps in maps()) {
+        let seeds: Vec<u32> = seeds.iter().flat_map(|&(start, len)| [start, len]).collect();
+        let run = aoc::run::<Solution>(&render(&seeds, &maps)).unwrap();
+        maps.iter_mut().for_each(|map| map.reverse());
+        prop_assert_eq!(aoc::run::<Solution>(&render(&seeds, &maps)).unwrap(), run);
+    }
+}
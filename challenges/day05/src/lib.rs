@@ -1,17 +1,22 @@
 #![feature(array_chunks)]
 #![feature(extend_one)]
 
-use std::{fmt::Display, ops::Range};
+use std::ops::Range;
 
-use aoc::Challenge;
+use aoc::{safe, Challenge, IntoAnswer};
 use nom::{
     bytes::complete::{tag, take_until},
     character::complete::line_ending,
+    combinator::cut,
+    error::ErrorKind,
     Parser,
 };
 use nom_supreme::ParserExt;
 use parsers::{number, ParserExt2 as _};
 
+#[cfg(test)]
+mod generate;
+
 #[derive(Debug, PartialEq, Clone, Copy)]
 struct MapRange {
     dst: u32,
@@ -19,24 +24,42 @@ struct MapRange {
     len: u32,
 }
 
+/// One of the numbers in a map range
+fn range_number(n: &str) -> Result<u32, safe::Error<'_>> {
+    n.parse().map_err(|_| safe::error(n, ErrorKind::Digit))
+}
+
 impl MapRange {
     fn parse(input: &str) -> nom::IResult<&str, Self> {
         if input.is_empty() || input.as_bytes()[0] == b'\n' {
-            return Err(nom::Err::Error(nom::error::Error::new(
-                input,
-                nom::error::ErrorKind::Digit,
-            )));
+            return Err(safe::error(input, ErrorKind::Digit));
         }
+        // the line has started, so any error is in the range rather than the end of the map
+        cut(Self::fields)(input)
+    }
 
-        let (dst, input) = input.split_once(' ').unwrap();
-        let (src, input) = input.split_once(' ').unwrap();
-        let (len, input) = input.split_once('\n').unwrap();
-
-        let dst = dst.parse().unwrap();
-        let src = src.parse().unwrap();
-        let len = len.parse().unwrap();
-
-        Ok((input, Self { dst, src, len }))
+    fn fields(input: &str) -> nom::IResult<&str, Self> {
+        let (dst, rest) = input
+            .split_once(' ')
+            .ok_or_else(|| safe::error(input, ErrorKind::Space))?;
+        let (src, rest) = rest
+            .split_once(' ')
+            .ok_or_else(|| safe::error(rest, ErrorKind::Space))?;
+        let (len, rest) = rest
+            .split_once('\n')
+            .ok_or_else(|| safe::error(rest, ErrorKind::CrLf))?;
+
+        let (dst, src, len) = (range_number(dst)?, range_number(src)?, range_number(len)?);
+        // the maps add offsets within a range without checking for overflow, so the last
+        // number in each range has to fit
+        let last = len.saturating_sub(1);
+        safe::ensure(
+            dst.checked_add(last).is_some() && src.checked_add(last).is_some(),
+            input,
+            ErrorKind::TooLarge,
+        )?;
+
+        Ok((rest, Self { dst, src, len }))
     }
 }
 
@@ -164,7 +187,7 @@ impl<'a> aoc::Parser<'a> for Solution {
 }
 
 impl Challenge for Solution {
-    fn part_one(self) -> impl Display {
+    fn part_one(self) -> impl IntoAnswer {
         let [soil, fertilizer, water, light, temp, humitiy, location] = self.maps;
 
         self.seeds
@@ -180,7 +203,7 @@ impl Challenge for Solution {
             .unwrap()
     }
 
-    fn part_two(self) -> impl Display {
+    fn part_two(self) -> impl IntoAnswer {
         let [soil, fertilizer, water, light, temp, humitiy, location] = self.maps;
 
         let mut ranges1 = self
@@ -205,61 +228,10 @@ impl Challenge for Solution {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::Solution;
-    use aoc::{Challenge, Parser};
-
-    const INPUT: &str = "seeds: 79 14 55 13
-
-seed-to-soil map:
-50 98 2
-52 50 48
-
-soil-to-fertilizer map:
-0 15 37
-37 52 2
-39 0 15
-
-fertilizer-to-water map:
-49 53 8
-0 11 42
-42 0 7
-57 7 4
-
-water-to-light map:
-88 18 7
-18 25 70
-
-light-to-temperature map:
-45 77 23
-81 45 19
-68 64 13
-
-temperature-to-humidity map:
-0 69 1
-1 0 69
-
-humidity-to-location map:
-60 56 37
-56 93 4
-";
-
-    #[test]
-    fn parse() {
-        let output = Solution::parse(INPUT).unwrap().1;
-        println!("{output:#?}");
-    }
-
-    #[test]
-    fn part_one() {
-        let output = Solution::parse(INPUT).unwrap().1;
-        assert_eq!(output.part_one().to_string(), "35");
-    }
-
-    #[test]
-    fn part_two() {
-        let output = Solution::parse(INPUT).unwrap().1;
-        assert_eq!(output.part_two().to_string(), "46");
-    }
-}
+aoc::solution!(
+    Solution,
+    year = 2023,
+    day = 5,
+    title = "If You Give A Seed A Fertilizer"
+);
+aoc::example_tests!(Solution);
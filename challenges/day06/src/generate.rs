@@ -0,0 +1,64 @@
+//! Up to four races whose records can be beaten, also once their digits are run together
+//! for part two, as the solution assumes there's a way to win. Records start at one, like the
+//! real input's, as the parser takes a number to end at the first space after a nonzero digit.
+
+use proptest::{collection::vec, prelude::*};
+
+use crate::Solution;
+
+/// Races as time and record distance
+type Race = (u64, u64);
+
+/// Whether some time holding the button beats the record
+fn winnable((time, record): Race) -> bool {
+    record < (time / 2) * (time - time / 2)
+}
+
+fn race() -> impl Strategy<Value = Race> {
+    (3..100u64).prop_flat_map(|time| (Just(time), 1..(time / 2) * (time - time / 2)))
+}
+
+fn races() -> impl Strategy<Value = Vec<Race>> {
+    vec(race(), 1..=4).prop_filter("the races run together can't be won", |races| {
+        let joined = |digits: fn(&Race) -> u64| -> u64 {
+            let digits: String = races.iter().map(|race| digits(race).to_string()).collect();
+            digits.parse().unwrap()
+        };
+        winnable((joined(|race| race.0), joined(|race| race.1)))
+    })
+}
+
+/// Both lines are padded to the same length, as the solution splits the input in half
+fn render(races: &[Race]) -> String {
+    let (mut time, mut distance) = (format!("{:<9}", "Time:"), "Distance:".to_owned());
+    for &(t, d) in races {
+        let width = 1 + t.to_string().len().max(d.to_string().len());
+        time += &format!(" {t:>width$}");
+        distance += &format!(" {d:>width$}");
+    }
+    format!("{time}\n{distance}\n")
+}
+
+proptest! {
+    /// Part one multiplies the races, so their order doesn't matter
+    #[test]
+    fn reordering_keeps_part_one(races in races()) {
+        let mut reversed = races.clone();
+        reversed.reverse();
+        prop_assert_eq!(
+            aoc::run_part_one::<Solution>(&render(&reversed)).unwrap(),
+            aoc::run_part_one::<Solution>(&render(&races)).unwrap()
+        );
+    }
+
+    /// Holding the button one millisecond longer in a race two longer goes `time + 1` further,
+    /// so against a record that much further it wins the same ways
+    #[test]
+    fn a_longer_race_wins_the_same_ways(race in race()) {
+        let (time, record) = race;
+        prop_assert_eq!(
+            aoc::run_part_one::<Solution>(&render(&[(time + 2, record + time + 1)])).unwrap(),
+            aoc::run_part_one::<Solution>(&render(&[race])).unwrap()
+        );
+    }
+}
@@ -1,7 +1,11 @@
-use std::fmt::Display;
+use std::ops::Range;
 
-use aoc::Challenge;
+use aoc::{safe, Challenge, IntoAnswer};
 use arrayvec::ArrayVec;
+use nom::error::ErrorKind;
+
+#[cfg(test)]
+mod generate;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Solution {
@@ -14,11 +18,10 @@ pub struct Solution {
 impl<'a> aoc::Parser<'a> for Solution {
     fn parse(input: &'a str) -> nom::IResult<&'a str, Self> {
         const PREFIX_LEN: usize = "Distance: ".len();
-        let bytes = input.as_bytes();
-        let line_len = bytes.len() / 2;
-        let (time, dist) = bytes.split_at(line_len);
-        let (time_list, time_join) = parse_line(&time[PREFIX_LEN..line_len - 1]);
-        let (dist_list, dist_join) = parse_line(&dist[PREFIX_LEN..line_len - 1]);
+        let line_len = input.len() / 2;
+        safe::ensure_at(line_len > PREFIX_LEN, input, input.len(), ErrorKind::Eof)?;
+        let (time_list, time_join) = parse_line(input, PREFIX_LEN..line_len - 1)?;
+        let (dist_list, dist_join) = parse_line(input, line_len + PREFIX_LEN..2 * line_len - 1)?;
 
         Ok((
             "",
@@ -32,16 +35,23 @@ impl<'a> aoc::Parser<'a> for Solution {
     }
 }
 
-fn parse_line(s: &[u8]) -> (ArrayVec<u64, 4>, u64) {
+/// Reads the numbers in `line` of `input`, and all of their digits as one number
+fn parse_line(input: &str, line: Range<usize>) -> Result<(ArrayVec<u64, 4>, u64), safe::Error<'_>> {
     let mut list = ArrayVec::new();
     let mut join = 0_u64;
     let mut indv = 0_u64;
 
-    for &b in s {
+    let start = line.start;
+    for (i, &b) in input.as_bytes()[line].iter().enumerate() {
         if b == b' ' && indv != 0 {
+            safe::ensure_at(!list.is_full(), input, start + i, ErrorKind::Count)?;
             list.push(indv);
             indv = 0;
         } else if b != b' ' {
+            safe::ensure_at(b.is_ascii_digit(), input, start + i, ErrorKind::Digit)?;
+            // the joined number is the largest, so it's the only one that can overflow
+            let fits = join <= (u64::MAX - 9) / 10;
+            safe::ensure_at(fits, input, start + i, ErrorKind::TooLarge)?;
             indv *= 10;
             join *= 10;
             let x = (b - b'0') as u64;
@@ -50,12 +60,13 @@ fn parse_line(s: &[u8]) -> (ArrayVec<u64, 4>, u64) {
         }
     }
 
+    safe::ensure_at(!list.is_full(), input, start, ErrorKind::Count)?;
     list.push(indv);
-    (list, join)
+    Ok((list, join))
 }
 
 impl Challenge for Solution {
-    fn part_one(self) -> impl Display {
+    fn part_one(self) -> impl IntoAnswer {
         if self.time_list.len() == 4 && self.dist_list.len() == 4 {
             std::iter::zip(self.time_list, self.dist_list)
                 .map(|(t, d)| solve(t, d))
@@ -67,7 +78,7 @@ impl Challenge for Solution {
         }
     }
 
-    fn part_two(self) -> impl Display {
+    fn part_two(self) -> impl IntoAnswer {
         let t = self.time_join;
         let d = self.dist_join;
         solve(t, d)
@@ -91,30 +102,5 @@ fn solve(t: u64, d: u64) -> u64 {
     (upper - lower) as u64 + 1
 }
 
-#[cfg(test)]
-mod tests {
-    use super::Solution;
-    use aoc::{Challenge, Parser};
-
-    const INPUT: &str = "Time:      7  15   30
-Distance:  9  40  200
-";
-
-    #[test]
-    fn parse() {
-        let output = Solution::parse(INPUT).unwrap().1;
-        println!("{output:?}");
-    }
-
-    #[test]
-    fn part_one() {
-        let output = Solution::parse(INPUT).unwrap().1;
-        assert_eq!(output.part_one().to_string(), "288");
-    }
-
-    #[test]
-    fn part_two() {
-        let output = Solution::parse(INPUT).unwrap().1;
-        assert_eq!(output.part_two().to_string(), "71503");
-    }
-}
+aoc::solution!(Solution, year = 2023, day = 6, title = "Wait For It");
+aoc::example_tests!(Solution);
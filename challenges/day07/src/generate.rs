@@ -0,0 +1,42 @@
+//! Sets of different hands, as the rankings have no way to break a tie, with bids like the
+//! real input's.
+
+use proptest::{collection::btree_map, prelude::*};
+
+use crate::Solution;
+
+type Hand = [u8; 5];
+
+fn hand(cards: &'static [u8]) -> impl Strategy<Value = Hand> {
+    prop::array::uniform5(prop::sample::select(cards))
+}
+
+fn hands(cards: &'static [u8]) -> impl Strategy<Value = Vec<(Hand, u32)>> {
+    btree_map(hand(cards), 1..=1000u32, 1..50)
+        .prop_map(|hands| hands.into_iter().collect::<Vec<_>>())
+        .prop_shuffle()
+}
+
+fn render(hands: &[(Hand, u32)]) -> String {
+    hands
+        .iter()
+        .map(|(hand, bid)| format!("{} {bid}\n", std::str::from_utf8(hand).unwrap()))
+        .collect()
+}
+
+proptest! {
+    /// Hands are ranked against each other, not by where they are in the list
+    #[test]
+    fn reordering_keeps_the_winnings(mut hands in hands(b"23456789TJQKA")) {
+        let run = aoc::run::<Solution>(&render(&hands)).unwrap();
+        hands.reverse();
+        prop_assert_eq!(aoc::run::<Solution>(&render(&hands)).unwrap(), run);
+    }
+
+    /// Without any `J`s there are no jokers, so both parts rank the hands the same
+    #[test]
+    fn no_jokers_agree(hands in hands(b"23456789TQKA")) {
+        let run = aoc::run::<Solution>(&render(&hands)).unwrap();
+        prop_assert_eq!(run.part_one, run.part_two);
+    }
+}
@@ -1,6 +1,8 @@
-use std::fmt::Display;
+use aoc::{safe, Challenge, IntoAnswer};
+use nom::error::ErrorKind;
 
-use aoc::Challenge;
+#[cfg(test)]
+mod generate;
 
 fn sort_five(x: [u8; 5]) -> [u8; 5] {
     let [a, b, c, d, e] = x;
@@ -151,8 +153,16 @@ fn parse_cards(input: &str) -> nom::IResult<&str, [u8; 5]> {
     //         nom::error::ErrorKind::AlphaNumeric,
     //     )));
     // }
-    let (hand, input) = input.split_at(5);
-    let [a, b, c, d, e]: [u8; 5] = hand.as_bytes().try_into().unwrap();
+    let [a, b, c, d, e] = safe::bytes(input)?;
+    // other bytes could pass for a joker, giving a hand `joker_hand` can't rank
+    safe::ensure(
+        [a, b, c, d, e]
+            .iter()
+            .all(|card| b"23456789TJQKA".contains(card)),
+        input,
+        ErrorKind::OneOf,
+    )?;
+    let input = safe::skip(input, 5)?;
     let cards = [
         parse_card(a),
         parse_card(b),
@@ -187,9 +197,12 @@ impl Bid {
 
         let mut bid = 0;
         let mut i = 1;
-        while input.as_bytes()[i] != b'\n' {
+        while safe::byte(input, i)? != b'\n' {
+            let digit = safe::byte(input, i)?;
+            safe::ensure_at(digit.is_ascii_digit(), input, i, ErrorKind::Digit)?;
+            safe::ensure_at(bid < u32::MAX / 10, input, i, ErrorKind::TooLarge)?;
             bid *= 10;
-            bid += (input.as_bytes()[i] & 0xf) as u32;
+            bid += (digit & 0xf) as u32;
             i += 1;
         }
 
@@ -200,7 +213,7 @@ impl Bid {
         let joker_hand = joker_hand(kind, cards);
 
         Ok((
-            &input[i + 1..],
+            safe::skip(input, i + 1)?,
             Self {
                 joker_hand,
                 hand,
@@ -226,7 +239,7 @@ impl<'a> aoc::Parser<'a> for Solution {
 }
 
 impl Challenge for Solution {
-    fn part_one(mut self) -> impl Display {
+    fn part_one(mut self) -> impl IntoAnswer {
         radsort::sort_by_key(&mut self.0, |a| a.hand);
         self.0
             .into_iter()
@@ -235,7 +248,7 @@ impl Challenge for Solution {
             .sum::<u32>()
     }
 
-    fn part_two(mut self) -> impl Display {
+    fn part_two(mut self) -> impl IntoAnswer {
         radsort::sort_by_key(&mut self.0, |a| a.joker_hand);
         self.0
             .into_iter()
@@ -245,33 +258,5 @@ impl Challenge for Solution {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::Solution;
-    use aoc::{Challenge, Parser};
-
-    const INPUT: &str = "32T3K 765
-T55J5 684
-KK677 28
-KTJJT 220
-QQQJA 483
-";
-
-    #[test]
-    fn parse() {
-        let output = Solution::parse(INPUT).unwrap().1;
-        println!("{output:?}");
-    }
-
-    #[test]
-    fn part_one() {
-        let output = Solution::parse(INPUT).unwrap().1;
-        assert_eq!(output.part_one().to_string(), "6440");
-    }
-
-    #[test]
-    fn part_two() {
-        let output = Solution::parse(INPUT).unwrap().1;
-        assert_eq!(output.part_two().to_string(), "5905");
-    }
-}
+aoc::solution!(Solution, year = 2023, day = 7, title = "Camel Cards");
+aoc::example_tests!(Solution);
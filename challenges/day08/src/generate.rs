@@ -0,0 +1,90 @@
+//! Networks laid out around one walk from `AAA` to `ZZZ` following the instructions, through
+//! nodes that neither start nor end a ghost's walk, with the turns not taken going anywhere.
+
+use proptest::{
+    collection::{btree_set, vec},
+    prelude::*,
+    sample::Index,
+};
+
+use crate::Solution;
+
+/// The instructions, `true` for right, and each node's name and left and right
+#[derive(Debug, Clone)]
+struct Network {
+    steps: Vec<bool>,
+    nodes: Vec<(String, [String; 2])>,
+}
+
+/// A name that doesn't end in `A` or `Z`
+fn name() -> impl Strategy<Value = String> {
+    let letter = |letters: std::ops::RangeInclusive<u8>| letters.prop_map(char::from);
+    (
+        letter(b'A'..=b'Z'),
+        letter(b'A'..=b'Z'),
+        letter(b'B'..=b'Y'),
+    )
+        .prop_map(|(a, b, c)| [a, b, c].iter().collect())
+}
+
+fn network() -> impl Strategy<Value = Network> {
+    (vec(any::<bool>(), 1..10), 1..30usize)
+        .prop_flat_map(|(steps, len)| {
+            let names = btree_set(name(), len - 1)
+                .prop_map(Vec::from_iter)
+                .prop_shuffle();
+            (Just(steps), names, vec(any::<Index>(), len))
+        })
+        .prop_map(|(steps, names, elsewhere)| {
+            let mut walk = vec!["AAA".to_owned()];
+            walk.extend(names);
+            walk.push("ZZZ".to_owned());
+
+            let mut nodes = vec![];
+            for (i, pair) in walk.windows(2).enumerate() {
+                let other = walk[elsewhere[i].index(walk.len())].clone();
+                let turns = if steps[i % steps.len()] {
+                    [other, pair[1].clone()]
+                } else {
+                    [pair[1].clone(), other]
+                };
+                nodes.push((pair[0].clone(), turns));
+            }
+            nodes.push(("ZZZ".to_owned(), ["ZZZ".to_owned(), "ZZZ".to_owned()]));
+            Network { steps, nodes }
+        })
+}
+
+fn render(network: &Network) -> String {
+    let steps: String = network
+        .steps
+        .iter()
+        .map(|&right| if right { 'R' } else { 'L' })
+        .collect();
+    let nodes: String = network
+        .nodes
+        .iter()
+        .map(|(name, [left, right])| format!("{name} = ({left}, {right})\n"))
+        .collect();
+    format!("{steps}\n\n{nodes}")
+}
+
+proptest! {
+    /// Both parts walk from `AAA`, the only start, to `ZZZ`, the only end
+    #[test]
+    fn both_parts_take_the_walk(network in network()) {
+        let run = aoc::run::<Solution>(&render(&network)).unwrap();
+        let steps = aoc::Answer::Int(network.nodes.len() as i128 - 1);
+        prop_assert_eq!(run.part_one, steps.clone());
+        prop_assert_eq!(run.part_two, steps);
+    }
+
+    /// Turning the other way at every step, and at every node, walks the same way
+    #[test]
+    fn swapping_left_and_right_keeps_the_walk(mut network in network()) {
+        let run = aoc::run::<Solution>(&render(&network)).unwrap();
+        network.steps.iter_mut().for_each(|right| *right = !*right);
+        network.nodes.iter_mut().for_each(|(_, turns)| turns.reverse());
+        prop_assert_eq!(aoc::run::<Solution>(&render(&network)).unwrap(), run);
+    }
+}
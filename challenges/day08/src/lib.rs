@@ -1,8 +1,12 @@
 use std::fmt;
 
-use aoc::Challenge;
+use aoc::{safe, Challenge, IntoAnswer};
+use nom::error::ErrorKind;
 use rayon::iter::{ParallelBridge, ParallelIterator};
 
+#[cfg(test)]
+mod generate;
+
 #[derive(PartialEq, Clone)]
 pub struct Solution<'a> {
     steps: &'a [u8],
@@ -41,7 +45,11 @@ impl fmt::Debug for Solution<'_> {
 
 impl<'a> aoc::Parser<'a> for Solution<'a> {
     fn parse(input: &'a str) -> nom::IResult<&'a str, Self> {
-        let (steps, rest) = input.split_once('\n').unwrap();
+        let Some((steps, rest)) = input.split_once('\n') else {
+            return Err(safe::error_at(input, input.len(), ErrorKind::CrLf));
+        };
+        // the parts cycle through the steps
+        safe::ensure(!steps.is_empty(), steps, ErrorKind::Many1)?;
         let mut input = rest.as_bytes();
         let mut paths: Box<[[u16; 2]; 32768]> = vec![[0, 0]; 32768].try_into().unwrap();
         while input.len() > 17 {
@@ -81,7 +89,7 @@ const fn lr(x: u8) -> u8 {
 }
 
 impl Challenge for Solution<'_> {
-    fn part_one(self) -> impl fmt::Display {
+    fn part_one(self) -> impl IntoAnswer {
         const GOAL: u16 = elem(*b"ZZZ");
         let mut state = elem(*b"AAA");
         let mut i = 0;
@@ -93,7 +101,7 @@ impl Challenge for Solution<'_> {
         i
     }
 
-    fn part_two(self) -> impl fmt::Display {
+    fn part_two(self) -> impl IntoAnswer {
         self.paths[1024..2048]
             .iter()
             .enumerate()
@@ -128,61 +136,18 @@ fn gcd(mut x: usize, mut y: usize) -> usize {
     x
 }
 
+aoc::solution!(
+    Solution<'static>,
+    year = 2023,
+    day = 8,
+    title = "Haunted Wasteland"
+);
+aoc::example_tests!(Solution<'static>);
+
 #[cfg(test)]
 mod tests {
     use crate::{elem, lr};
 
-    use super::Solution;
-    use aoc::{Challenge, Parser};
-
-    const INPUT1: &str = "RL
-
-AAA = (BBB, CCC)
-BBB = (DDD, EEE)
-CCC = (ZZZ, GGG)
-DDD = (DDD, DDD)
-EEE = (EEE, EEE)
-GGG = (GGG, GGG)
-ZZZ = (ZZZ, ZZZ)
-";
-    const INPUT2: &str = "LLR
-
-AAA = (BBB, BBB)
-BBB = (AAA, ZZZ)
-ZZZ = (ZZZ, ZZZ)
-";
-    const INPUT3: &str = "LR
-
-FFA = (FFB, XXX)
-FFB = (XXX, FFZ)
-FFZ = (FFB, XXX)
-GGA = (GGB, XXX)
-GGB = (GGC, GGC)
-GGC = (GGZ, GGZ)
-GGZ = (GGB, GGB)
-XXX = (XXX, XXX)
-";
-
-    #[test]
-    fn parse() {
-        let output = Solution::parse(INPUT3).unwrap().1;
-        println!("{output:?}");
-    }
-
-    #[test]
-    fn part_one() {
-        let output = Solution::parse(INPUT1).unwrap().1;
-        assert_eq!(output.part_one().to_string(), "2");
-        let output = Solution::parse(INPUT2).unwrap().1;
-        assert_eq!(output.part_one().to_string(), "6");
-    }
-
-    #[test]
-    fn part_two() {
-        let output = Solution::parse(INPUT3).unwrap().1;
-        assert_eq!(output.part_two().to_string(), "6");
-    }
-
     #[test]
     fn test_lr() {
         assert_eq!(lr(b'L'), 0);
@@ -0,0 +1,35 @@
+//! Reports whose histories are all the same length, as the solution reads it off the first.
+
+use proptest::{collection::vec, prelude::*};
+
+use crate::Solution;
+
+/// Histories of 6 values like the example, or 21 like the real input
+fn histories() -> impl Strategy<Value = Vec<Vec<i64>>> {
+    prop_oneof![Just(6), Just(21)].prop_flat_map(|len| vec(vec(-1000i64..1000, len), 1..20))
+}
+
+fn render(histories: &[Vec<i64>]) -> String {
+    histories
+        .iter()
+        .map(|h| {
+            let values: Vec<_> = h.iter().map(|x| x.to_string()).collect();
+            values.join(" ") + "\n"
+        })
+        .collect()
+}
+
+pub fn inputs() -> impl Strategy<Value = String> {
+    histories().prop_map(|histories| render(&histories))
+}
+
+proptest! {
+    /// Extrapolating backwards is extrapolating forwards through the reversed histories
+    #[test]
+    fn backwards_is_reversed_forwards(mut histories in histories()) {
+        let backwards = aoc::run_part_two::<Solution>(&render(&histories)).unwrap();
+        histories.iter_mut().for_each(|h| h.reverse());
+        let forwards = aoc::run_part_one::<Solution>(&render(&histories)).unwrap();
+        prop_assert_eq!(backwards, forwards);
+    }
+}
@@ -1,6 +1,10 @@
-use std::fmt;
+use aoc::{safe, Challenge, IntoAnswer};
+use nom::error::ErrorKind;
 
-use aoc::Challenge;
+#[cfg(test)]
+mod generate;
+#[cfg(test)]
+mod reference;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Solution {
@@ -14,12 +18,18 @@ impl<'a> aoc::Parser<'a> for Solution {
         let mut all = Vec::with_capacity(200 * 21);
         while !input.is_empty() {
             loop {
-                let i = input.find([' ', '\n']).unwrap();
+                let Some(i) = input.find([' ', '\n']) else {
+                    return Err(safe::error_at(input, input.len(), ErrorKind::CrLf));
+                };
                 let c = input.as_bytes()[i];
 
                 let (start, input2) = input.split_at(i);
                 input = &input2[1..];
-                all.push(start.parse().unwrap());
+                all.push(
+                    start
+                        .parse()
+                        .map_err(|_| safe::error(start, ErrorKind::Digit))?,
+                );
 
                 if c == b'\n' {
                     break;
@@ -35,7 +45,7 @@ impl<'a> aoc::Parser<'a> for Solution {
 }
 
 impl Challenge for Solution {
-    fn part_one(self) -> impl fmt::Display {
+    fn part_one(self) -> impl IntoAnswer {
         if self.len == 21 {
             self.all.chunks_exact(21).map(formula21).sum::<i64>()
         } else {
@@ -43,7 +53,7 @@ impl Challenge for Solution {
         }
     }
 
-    fn part_two(self) -> impl fmt::Display {
+    fn part_two(self) -> impl IntoAnswer {
         if self.len == 21 {
             self.all.chunks_exact(21).map(formula21m1).sum::<i64>()
         } else {
@@ -127,31 +137,6 @@ fn formula6m1(ys: &[i64]) -> i64 {
     sum
 }
 
-#[cfg(test)]
-mod tests {
-    use super::Solution;
-    use aoc::{Challenge, Parser};
-
-    const INPUT: &str = "0 3 6 9 12 15
-1 3 6 10 15 21
-10 13 16 21 30 45
-";
-
-    #[test]
-    fn parse() {
-        let output = Solution::parse(INPUT).unwrap().1;
-        println!("{output:?}");
-    }
-
-    #[test]
-    fn part_one() {
-        let output = Solution::parse(INPUT).unwrap().1;
-        assert_eq!(output.part_one().to_string(), "114");
-    }
-
-    #[test]
-    fn part_two() {
-        let output = Solution::parse(INPUT).unwrap().1;
-        assert_eq!(output.part_two().to_string(), "2");
-    }
-}
+aoc::solution!(Solution, year = 2023, day = 9, title = "Mirage Maintenance");
+aoc::example_tests!(Solution);
+aoc::differential_tests!(Solution, reference::Naive, inputs = generate::inputs);
@@ -0,0 +1,34 @@
+//! Extrapolates each history by taking differences until they're all zero, as the puzzle describes.
+
+pub struct Naive;
+
+fn histories(input: &str) -> impl Iterator<Item = Vec<i64>> + '_ {
+    input.lines().map(|line| {
+        line.split_whitespace()
+            .map(|n| n.parse().unwrap())
+            .collect()
+    })
+}
+
+fn next(history: &[i64]) -> i64 {
+    if history.iter().all(|&x| x == 0) {
+        return 0;
+    }
+    let differences: Vec<_> = history.windows(2).map(|w| w[1] - w[0]).collect();
+    history[history.len() - 1] + next(&differences)
+}
+
+impl aoc::Reference for Naive {
+    fn part_one(input: &str) -> impl aoc::IntoAnswer {
+        histories(input).map(|h| next(&h)).sum::<i64>()
+    }
+
+    fn part_two(input: &str) -> impl aoc::IntoAnswer {
+        histories(input)
+            .map(|mut h| {
+                h.reverse();
+                next(&h)
+            })
+            .sum::<i64>()
+    }
+}
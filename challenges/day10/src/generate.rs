@@ -0,0 +1,154 @@
+//! Mazes the size of the larger examples, their loop tracing a shape that can't cross itself,
+//! with junk around it that never connects to the `S`.
+
+use proptest::{collection::vec, prelude::*};
+
+use crate::Solution;
+
+const WIDTH: usize = 20;
+const HEIGHT: usize = 10;
+
+/// A 20 by 10 maze like the larger examples, the loop running around a shape made
+/// of columns, each a span of rows overlapping the last. Tiles off the loop are junk.
+fn maze() -> impl Strategy<Value = Vec<Vec<u8>>> {
+    (
+        vec((0..HEIGHT, 0..HEIGHT), 1..WIDTH),
+        vec(
+            vec(prop::sample::select(b".|-LJ7F".to_vec()), WIDTH),
+            HEIGHT,
+        ),
+    )
+        .prop_map(|(spans, mut tiles)| {
+            let mut columns: Vec<(usize, usize)> = Vec::new();
+            for (a, b) in spans {
+                let (mut lo, mut hi) = (a.min(b), a.max(b));
+                if lo == hi {
+                    (lo, hi) = if hi < HEIGHT - 1 {
+                        (lo, hi + 1)
+                    } else {
+                        (lo - 1, hi)
+                    };
+                }
+                if let Some(&(last_lo, last_hi)) = columns.last() {
+                    // overlap the previous column by at least one row
+                    lo = lo.min(last_hi - 1);
+                    hi = hi.max(last_lo + 1);
+                }
+                columns.push((lo, hi));
+            }
+
+            // corners clockwise from the top left
+            let mut corners = vec![];
+            for (x, &(lo, _)) in columns.iter().enumerate() {
+                corners.extend([(x, lo), (x + 1, lo)]);
+            }
+            for (x, &(_, hi)) in columns.iter().enumerate().rev() {
+                corners.extend([(x + 1, hi), (x, hi)]);
+            }
+            corners.push(corners[0]);
+
+            let mut path = vec![corners[0]];
+            for pair in corners.windows(2) {
+                let [(x0, y0), (x1, y1)] = [pair[0], pair[1]];
+                let (mut x, mut y) = (x0, y0);
+                while (x, y) != (x1, y1) {
+                    x = if x1 > x {
+                        x + 1
+                    } else if x1 < x {
+                        x - 1
+                    } else {
+                        x
+                    };
+                    y = if y1 > y {
+                        y + 1
+                    } else if y1 < y {
+                        y - 1
+                    } else {
+                        y
+                    };
+                    path.push((x, y));
+                }
+            }
+            // the loop is closed, so the last step is back onto the first tile
+            path.pop();
+
+            for (i, &(x, y)) in path.iter().enumerate() {
+                let prev = path[(i + path.len() - 1) % path.len()];
+                let next = path[(i + 1) % path.len()];
+                tiles[y][x] = pipe((x, y), prev, next);
+            }
+
+            // keep junk next to the start from looking like it connects to it
+            let (sx, sy) = path[0];
+            tiles[sy][sx] = b'S';
+            for (x, y, opening) in [
+                (sx.wrapping_sub(1), sy, b"-LF"),
+                (sx + 1, sy, b"-J7"),
+                (sx, sy.wrapping_sub(1), b"|7F"),
+                (sx, sy + 1, b"|LJ"),
+            ] {
+                let on_loop = path.contains(&(x, y));
+                if let Some(tile) = tiles.get_mut(y).and_then(|row| row.get_mut(x)) {
+                    if !on_loop && opening.contains(tile) {
+                        *tile = b'.';
+                    }
+                }
+            }
+            tiles
+        })
+}
+
+/// The pipe at `at` joining its neighbours on the loop
+fn pipe(at: (usize, usize), prev: (usize, usize), next: (usize, usize)) -> u8 {
+    let side = |(x, y): (usize, usize)| match (x.cmp(&at.0), y.cmp(&at.1)) {
+        (std::cmp::Ordering::Less, _) => 'W',
+        (std::cmp::Ordering::Greater, _) => 'E',
+        (_, std::cmp::Ordering::Less) => 'N',
+        _ => 'S',
+    };
+    let mut sides = [side(prev), side(next)];
+    sides.sort_unstable();
+    match sides {
+        ['N', 'S'] => b'|',
+        ['E', 'W'] => b'-',
+        ['E', 'N'] => b'L',
+        ['N', 'W'] => b'J',
+        ['S', 'W'] => b'7',
+        ['E', 'S'] => b'F',
+        _ => unreachable!("{sides:?}"),
+    }
+}
+
+fn render(tiles: &[Vec<u8>]) -> &'static str {
+    let mut input = String::new();
+    for row in tiles {
+        input.extend(row.iter().map(|&t| t as char));
+        input.push('\n');
+    }
+    aoc::leak_input(input)
+}
+
+pub fn inputs() -> impl Strategy<Value = String> {
+    maze().prop_map(|tiles| render(&tiles).to_owned())
+}
+
+proptest! {
+    /// Mirroring the maze left to right doesn't change the loop's length or what it encloses
+    #[test]
+    fn mirroring_keeps_the_loop(mut tiles in maze()) {
+        let run = aoc::run::<Solution>(render(&tiles)).unwrap();
+        for row in &mut tiles {
+            row.reverse();
+            for tile in row {
+                *tile = match *tile {
+                    b'L' => b'J',
+                    b'J' => b'L',
+                    b'7' => b'F',
+                    b'F' => b'7',
+                    t => t,
+                };
+            }
+        }
+        prop_assert_eq!(aoc::run::<Solution>(render(&tiles)).unwrap(), run);
+    }
+}
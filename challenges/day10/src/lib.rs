@@ -1,7 +1,11 @@
-use std::fmt;
-
-use aoc::Challenge;
+use aoc::{safe, Challenge, IntoAnswer};
 use arrayvec::ArrayVec;
+use nom::error::ErrorKind;
+
+#[cfg(test)]
+mod generate;
+#[cfg(test)]
+mod reference;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Solution<'a> {
@@ -84,7 +88,7 @@ impl Dir {
 
 impl<'a> aoc::Parser<'a> for Solution<'a> {
     fn parse(input: &'a str) -> nom::IResult<&'a str, Self> {
-        let data = unsafe { std::mem::transmute::<&[u8], &[Foo]>(input.as_bytes()) };
+        let data = unsafe { safe::cast::<Foo>(input, |b| b"|-LJ7F.S\n".contains(&b))? };
         let (width, widthd, height) = if 140 * 141 == input.len() {
             (141, u64::MAX / 141 + 1, 140)
         } else if input.len() == 210 {
@@ -92,19 +96,31 @@ impl<'a> aoc::Parser<'a> for Solution<'a> {
         } else {
             (6, u64::MAX / 6 + 1, 5)
         };
+        if safe::ENABLED && data.len() != (width * height) as usize {
+            // the length picked the grid, so go by the first line to find the one that's off
+            safe::lines(input, input.find('\n').map_or(input.len(), |i| i + 1))?;
+            return Err(safe::error(input, ErrorKind::LengthValue));
+        }
 
-        let s = data.iter().position(|x| *x == Foo::Start).unwrap() as u32;
+        let s = match data.iter().position(|x| *x == Foo::Start) {
+            Some(s) => s as u32,
+            // the grid ran out without the `S` the loop starts from
+            None => return Err(safe::error_at(input, input.len(), ErrorKind::Eof)),
+        };
 
         let mut pipes = ArrayVec::<(u32, Dir), 2>::new();
         let dirs = [Dir::East, Dir::West, Dir::North, Dir::South];
         for dir in dirs {
             if let Some(j) = dir.apply(s, width, height, widthd) {
                 if let Some(x) = data[j as usize].map(dir) {
+                    safe::ensure_at(!pipes.is_full(), input, s as usize, ErrorKind::Verify)?;
                     pipes.push((j, x));
                 }
             }
         }
-        let [(start, start_dir), (end, _)] = pipes.into_inner().unwrap();
+        let Ok([(start, start_dir), (end, _)]) = pipes.into_inner() else {
+            return Err(safe::error_at(input, s as usize, ErrorKind::Verify));
+        };
 
         Ok((
             "",
@@ -123,13 +139,13 @@ impl<'a> aoc::Parser<'a> for Solution<'a> {
 }
 
 impl Challenge for Solution<'_> {
-    fn part_one(self) -> impl fmt::Display {
+    fn part_one(self) -> impl IntoAnswer {
         let mut len = 1;
         self.walk(|_| len += 1);
         len / 2
     }
 
-    fn part_two(self) -> impl fmt::Display {
+    fn part_two(self) -> impl IntoAnswer {
         // shoelace formula:
         // 2*area = sum(y[i] * (x[i-1] - x[i+1]))
         // picks theorem:
@@ -174,59 +190,15 @@ impl Solution<'_> {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::Solution;
-    use aoc::{Challenge, Parser};
-
-    const INPUT: &str = "7-F7-
-.FJ|7
-SJLL7
-|F--J
-LJ.LJ
-";
-
-    const INPUT2: &str = ".F----7F7F7F7F-7....
-.|F--7||||||||FJ....
-.||.FJ||||||||L7....
-FJL7L7LJLJ||LJ.L-7..
-L--J.L7...LJS7F-7L7.
-....F-J..F7FJ|L7L7L7
-....L7.F7||L7|.L7L7|
-.....|FJLJ|FJ|F7|.LJ
-....FJL-7.||.||||...
-....L---J.LJ.LJLJ...
-";
-
-    const INPUT3: &str = "FF7FSF7F7F7F7F7F---7
-L|LJ||||||||||||F--J
-FL-7LJLJ||||||LJL-77
-F--JF--7||LJLJ7F7FJ-
-L---JF-JLJ.||-FJLJJ7
-|F|F-JF---7F7-L7L|7|
-|FFJF7L7F-JF7|JL---7
-7-L-JL7||F7|L7F-7F7|
-L.L7LFJ|||||FJL7||LJ
-L7JLJL-JLJLJL--JLJ.L
-";
-
-    #[test]
-    fn parse() {
-        let output = Solution::parse(INPUT).unwrap().1;
-        println!("{output:?}");
-    }
-
-    #[test]
-    fn part_one() {
-        let output = Solution::parse(INPUT).unwrap().1;
-        assert_eq!(output.part_one().to_string(), "8");
-    }
-
-    #[test]
-    fn part_two() {
-        let output = Solution::parse(INPUT2).unwrap().1;
-        assert_eq!(output.part_two().to_string(), "8");
-        let output = Solution::parse(INPUT3).unwrap().1;
-        assert_eq!(output.part_two().to_string(), "10");
-    }
-}
+aoc::solution!(
+    Solution<'static>,
+    year = 2023,
+    day = 10,
+    title = "Pipe Maze"
+);
+aoc::example_tests!(Solution<'static>);
+aoc::differential_tests!(
+    Solution<'static>,
+    reference::Naive,
+    inputs = generate::inputs
+);
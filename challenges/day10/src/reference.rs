@@ -0,0 +1,104 @@
+//! Finds the loop with a breadth first search, and the enclosed tiles by counting
+//! how many times each row crosses the loop, rather than with the shoelace formula.
+
+use std::collections::VecDeque;
+
+pub struct Naive;
+
+const NORTH: (isize, isize) = (0, -1);
+const SOUTH: (isize, isize) = (0, 1);
+const EAST: (isize, isize) = (1, 0);
+const WEST: (isize, isize) = (-1, 0);
+
+/// The directions a tile's pipe opens towards
+fn openings(tile: u8) -> &'static [(isize, isize)] {
+    match tile {
+        b'|' => &[NORTH, SOUTH],
+        b'-' => &[EAST, WEST],
+        b'L' => &[NORTH, EAST],
+        b'J' => &[NORTH, WEST],
+        b'7' => &[SOUTH, WEST],
+        b'F' => &[SOUTH, EAST],
+        _ => &[],
+    }
+}
+
+struct Maze {
+    tiles: Vec<Vec<u8>>,
+    /// How far each tile on the loop is from the start, along the loop
+    distances: Vec<Vec<Option<usize>>>,
+}
+
+impl Maze {
+    fn new(input: &str) -> Self {
+        let mut tiles: Vec<Vec<u8>> = input.lines().map(|l| l.bytes().collect()).collect();
+        let (sx, sy) = tiles
+            .iter()
+            .enumerate()
+            .find_map(|(y, row)| Some((row.iter().position(|&t| t == b'S')?, y)))
+            .unwrap();
+        let tile = |x: isize, y: isize| {
+            let row = tiles.get(usize::try_from(y).ok()?)?;
+            row.get(usize::try_from(x).ok()?).copied()
+        };
+
+        // the start is whichever pipe connects to both of its neighbours that connect back
+        let connected: Vec<_> = [NORTH, SOUTH, EAST, WEST]
+            .into_iter()
+            .filter(|&(dx, dy)| {
+                let neighbour = tile(sx as isize + dx, sy as isize + dy).unwrap_or(b'.');
+                openings(neighbour).contains(&(-dx, -dy))
+            })
+            .collect();
+        let start = b"|-LJ7F"
+            .iter()
+            .copied()
+            .find(|&t| connected.iter().all(|d| openings(t).contains(d)))
+            .unwrap();
+        tiles[sy][sx] = start;
+
+        let mut distances = vec![vec![None; tiles[0].len()]; tiles.len()];
+        distances[sy][sx] = Some(0);
+        let mut queue = VecDeque::from([(sx, sy, 0)]);
+        while let Some((x, y, distance)) = queue.pop_front() {
+            for &(dx, dy) in openings(tiles[y][x]) {
+                let (x, y) = ((x as isize + dx) as usize, (y as isize + dy) as usize);
+                if distances[y][x].is_none() {
+                    distances[y][x] = Some(distance + 1);
+                    queue.push_back((x, y, distance + 1));
+                }
+            }
+        }
+        Self { tiles, distances }
+    }
+}
+
+impl aoc::Reference for Naive {
+    fn part_one(input: &str) -> impl aoc::IntoAnswer {
+        let maze = Maze::new(input);
+        maze.distances
+            .iter()
+            .flatten()
+            .flatten()
+            .copied()
+            .max()
+            .unwrap()
+    }
+
+    fn part_two(input: &str) -> impl aoc::IntoAnswer {
+        let maze = Maze::new(input);
+        let mut enclosed = 0;
+        for (row, distances) in maze.tiles.iter().zip(&maze.distances) {
+            // crossing a pipe that leads north flips whether we're inside
+            let mut inside = false;
+            for (&tile, distance) in row.iter().zip(distances) {
+                if distance.is_some() {
+                    inside ^= openings(tile).contains(&NORTH);
+                } else if inside {
+                    enclosed += 1;
+                }
+            }
+        }
+        enclosed
+    }
+}
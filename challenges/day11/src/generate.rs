@@ -0,0 +1,39 @@
+//! Images the size of the example, the only one the solution knows besides the real input's.
+
+use proptest::{collection::vec, prelude::*};
+
+use crate::Solution;
+
+type Image = Vec<Vec<bool>>;
+
+/// A 10 by 10 image like the example, the only size besides the real input's
+fn image() -> impl Strategy<Value = Image> {
+    vec(vec(prop::bool::weighted(0.15), 10), 10)
+}
+
+fn render(image: &Image) -> &'static str {
+    let input: String = image
+        .iter()
+        .map(|row| {
+            let mut line: String = row.iter().map(|&g| if g { '#' } else { '.' }).collect();
+            line.push('\n');
+            line
+        })
+        .collect();
+    aoc::leak_input(input)
+}
+
+fn transpose(image: &Image) -> Image {
+    (0..image[0].len())
+        .map(|x| image.iter().map(|row| row[x]).collect())
+        .collect()
+}
+
+proptest! {
+    /// Distances between galaxies don't care which way round the image is
+    #[test]
+    fn transposing_keeps_the_distances(image in image()) {
+        let run = aoc::run::<Solution>(render(&image)).unwrap();
+        prop_assert_eq!(aoc::run::<Solution>(render(&transpose(&image))).unwrap(), run);
+    }
+}
@@ -1,6 +1,7 @@
-use std::fmt;
+use aoc::{safe, Challenge, IntoAnswer};
 
-use aoc::Challenge;
+#[cfg(test)]
+mod generate;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Solution<'a> {
@@ -20,7 +21,7 @@ enum Foo {
 
 impl<'a> aoc::Parser<'a> for Solution<'a> {
     fn parse(input: &'a str) -> nom::IResult<&'a str, Self> {
-        let data = unsafe { std::mem::transmute::<&[u8], &[Foo]>(input.as_bytes()) };
+        let data = unsafe { safe::cast::<Foo>(input, |b| matches!(b, b'.' | b'#' | b'\n'))? };
         let (width, height) = if 140 * 141 == input.len() {
             (141, 140)
         } else {
@@ -38,7 +39,7 @@ impl<'a> aoc::Parser<'a> for Solution<'a> {
 }
 
 impl Solution<'_> {
-    fn inner<const N: usize>(self) -> impl fmt::Display {
+    fn inner<const N: usize>(self) -> impl IntoAnswer {
         let mut cols = vec![0u8; self.width];
         let mut sum = 0;
         let mut last_y = 0;
@@ -86,19 +87,27 @@ impl Solution<'_> {
 }
 
 impl Challenge for Solution<'_> {
-    fn part_one(self) -> impl fmt::Display {
+    fn part_one(self) -> impl IntoAnswer {
         self.inner::<2>()
     }
 
-    fn part_two(self) -> impl fmt::Display {
+    fn part_two(self) -> impl IntoAnswer {
         self.inner::<1000000>()
     }
 }
 
+aoc::solution!(
+    Solution<'static>,
+    year = 2023,
+    day = 11,
+    title = "Cosmic Expansion"
+);
+aoc::example_tests!(Solution<'static>, part_one);
+
 #[cfg(test)]
 mod tests {
     use super::Solution;
-    use aoc::{Challenge, Parser};
+    use aoc::Parser;
 
     // s0 = 1 + 2 + 5 + 6 + 7 + 10 + 11 + 11 = (s1 + 8 * 1)
     // s1 =     1 + 4 + 5 + 6 +  9 + 10 + 10 = (s2 + 7 * 1)
@@ -121,18 +130,6 @@ mod tests {
 #...#.....
 ";
 
-    #[test]
-    fn parse() {
-        let output = Solution::parse(INPUT).unwrap().1;
-        println!("{output:?}");
-    }
-
-    #[test]
-    fn part_one() {
-        let output = Solution::parse(INPUT).unwrap().1;
-        assert_eq!(output.part_one().to_string(), "374");
-    }
-
     #[test]
     fn part_two() {
         let output = Solution::parse(INPUT).unwrap().1;
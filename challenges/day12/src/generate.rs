@@ -0,0 +1,70 @@
+//! Rows laid out from their runs, so that each has an arrangement, with some springs then
+//! hidden. There are at most six runs, as part two repeats them five times over in an
+//! `ArrayVec` of 32.
+
+use proptest::{collection::vec, prelude::*};
+
+use crate::Solution;
+
+/// The springs and the runs of damaged ones
+type Row = (Vec<u8>, Vec<u8>);
+
+fn row(unknown: f64) -> impl Strategy<Value = Row> {
+    vec(1..=4u8, 1..=6)
+        .prop_flat_map(move |runs| {
+            let gaps = vec(0..3usize, runs.len() + 1);
+            let cells = runs.iter().sum::<u8>() as usize + 3 * runs.len() + 3;
+            (Just(runs), gaps, vec(prop::bool::weighted(unknown), cells))
+        })
+        .prop_map(|(runs, gaps, hidden)| {
+            let mut springs = vec![b'.'; gaps[0]];
+            for (i, &run) in runs.iter().enumerate() {
+                springs.extend(std::iter::repeat_n(b'#', run as usize));
+                // runs need an operational spring between them
+                let gap = gaps[i + 1] + (i + 1 < runs.len()) as usize;
+                springs.extend(std::iter::repeat_n(b'.', gap));
+            }
+            for (spring, hidden) in springs.iter_mut().zip(hidden) {
+                if hidden {
+                    *spring = b'?';
+                }
+            }
+            (springs, runs)
+        })
+}
+
+fn render(rows: &[Row]) -> String {
+    rows.iter()
+        .map(|(springs, runs)| {
+            let runs: Vec<String> = runs.iter().map(u8::to_string).collect();
+            format!(
+                "{} {}\n",
+                std::str::from_utf8(springs).unwrap(),
+                runs.join(",")
+            )
+        })
+        .collect()
+}
+
+proptest! {
+    /// Arrangements read the same from either end
+    #[test]
+    fn reversing_keeps_the_arrangements(rows in vec(row(0.4), 1..10)) {
+        let run = aoc::run::<Solution>(&render(&rows)).unwrap();
+        let mut reversed = rows;
+        for (springs, runs) in &mut reversed {
+            springs.reverse();
+            runs.reverse();
+        }
+        prop_assert_eq!(aoc::run::<Solution>(&render(&reversed)).unwrap(), run);
+    }
+
+    /// A row with nothing hidden has just the one arrangement, even unfolded
+    #[test]
+    fn known_rows_have_one_arrangement(rows in vec(row(0.0), 1..10)) {
+        let run = aoc::run::<Solution>(&render(&rows)).unwrap();
+        let rows = aoc::Answer::Int(rows.len() as i128);
+        prop_assert_eq!(run.part_one, rows.clone());
+        prop_assert_eq!(run.part_two, rows);
+    }
+}
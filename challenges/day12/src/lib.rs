@@ -1,9 +1,14 @@
-use std::{borrow::Cow, fmt};
+use std::borrow::Cow;
 
+use aoc::safe;
 use arrayvec::ArrayVec;
+use nom::error::ErrorKind;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use rustc_hash::FxHashMap;
 
+#[cfg(test)]
+mod generate;
+
 #[derive(Debug, PartialEq, Clone, Hash, Eq)]
 struct Line<'a> {
     springs: Cow<'a, [Spring]>,
@@ -26,24 +31,36 @@ impl<'a> aoc::Parser<'a> for Solution<'a> {
     fn parse(mut input: &'a str) -> nom::IResult<&'a str, Self> {
         let mut out = Vec::with_capacity(1000);
         while !input.is_empty() {
-            let (springs, rest) = input.split_once(' ').unwrap();
-            let (numbers, rest) = rest.split_once('\n').unwrap();
+            let Some((springs, rest)) = input.split_once(' ') else {
+                let end = input.find('\n').unwrap_or(input.len());
+                return Err(safe::error_at(input, end, ErrorKind::Space));
+            };
+            let Some((numbers, rest)) = rest.split_once('\n') else {
+                return Err(safe::error_at(rest, rest.len(), ErrorKind::CrLf));
+            };
             input = rest;
 
-            let springs = unsafe { std::mem::transmute::<&[u8], &[Spring]>(springs.as_bytes()) };
+            let springs = unsafe { safe::cast::<Spring>(springs, |b| b".#?".contains(&b))? };
             let mut runs = ArrayVec::new();
             let mut n = 0;
-            for b in numbers.bytes() {
+            let mut start = 0;
+            for (i, b) in numbers.bytes().enumerate() {
                 match b {
                     b',' => {
+                        safe::ensure_at(!runs.is_full(), numbers, start, ErrorKind::Count)?;
                         runs.push(n);
                         n = 0;
+                        start = i + 1;
                     }
                     _ => {
+                        safe::ensure_at(b.is_ascii_digit(), numbers, i, ErrorKind::Digit)?;
+                        let fits = n <= (u8::MAX - 9) / 10;
+                        safe::ensure_at(fits, numbers, start, ErrorKind::TooLarge)?;
                         n = 10 * n + (b & 0xf);
                     }
                 }
             }
+            safe::ensure_at(!runs.is_full(), numbers, start, ErrorKind::Count)?;
             runs.push(n);
             out.push(Line {
                 springs: Cow::Borrowed(springs),
@@ -93,7 +110,7 @@ impl<'a> Line<'a> {
         }
     }
 
-    fn into_part_two(self) -> Line<'static> {
+    fn to_part_two(&self) -> Line<'static> {
         let mut springs = Vec::with_capacity(self.springs.len() * 5 + 4);
         springs.extend_from_slice(&self.springs);
         springs.push(Spring::Unknown);
@@ -190,10 +207,9 @@ impl<'a> LineRef<'a> {
 }
 
 impl Solution<'_> {
-    fn part_one(self) -> impl fmt::Display {
-        let this = self.0;
-
-        this.par_iter()
+    fn part_one(&self) -> impl aoc::IntoAnswer {
+        self.0
+            .par_iter()
             .map(|l| l.as_ref())
             .map_init(
                 || FxHashMap::with_capacity_and_hasher(1024, Default::default()),
@@ -202,12 +218,8 @@ impl Solution<'_> {
             .sum::<u64>()
     }
 
-    fn part_two(self) -> impl fmt::Display {
-        let this = self
-            .0
-            .into_iter()
-            .map(Line::into_part_two)
-            .collect::<Vec<_>>();
+    fn part_two(&self) -> impl aoc::IntoAnswer {
+        let this = self.0.iter().map(Line::to_part_two).collect::<Vec<_>>();
 
         this.par_iter()
             .map(|l| l.as_ref())
@@ -224,44 +236,20 @@ impl Solution<'_> {
 //     Solution::parse(input).unwrap().1.part_two()
 // }
 
-impl aoc::Challenge for Solution<'_> {
-    fn part_one(self) -> impl std::fmt::Display {
+impl aoc::ChallengeRef for Solution<'_> {
+    fn part_one(&self) -> impl aoc::IntoAnswer {
         self.part_one()
     }
 
-    fn part_two(self) -> impl std::fmt::Display {
+    fn part_two(&self) -> impl aoc::IntoAnswer {
         self.part_two()
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::Solution;
-    use aoc::Parser;
-
-    const INPUT: &str = "???.### 1,1,3
-.??..??...?##. 1,1,3
-?#?#?#?#?#?#?#? 1,3,1,6
-????.#...#... 4,1,1
-????.######..#####. 1,6,5
-?###???????? 3,2,1
-";
-
-    #[test]
-    fn parse() {
-        let output = Solution::must_parse(INPUT);
-        println!("{output:?}");
-    }
-
-    #[test]
-    fn part_one() {
-        let output = Solution::must_parse(INPUT);
-        assert_eq!(output.part_one().to_string(), "21");
-    }
-
-    #[test]
-    fn part_two() {
-        let output = Solution::must_parse(INPUT);
-        assert_eq!(output.part_two().to_string(), "525152");
-    }
-}
+aoc::solution!(
+    Solution<'static>,
+    year = 2023,
+    day = 12,
+    title = "Hot Springs"
+);
+aoc::example_tests!(Solution<'static>);
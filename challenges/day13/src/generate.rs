@@ -0,0 +1,84 @@
+//! Patterns small enough for the solution's fixed-size rows and columns, most of them
+//! folded so that there is a reflection to find.
+
+use proptest::{collection::vec, prelude::*};
+
+use crate::Solution;
+
+type Pattern = Vec<Vec<bool>>;
+
+/// A pattern up to 17 by 17, usually folded about some row or column so that it reflects,
+/// then with a cell or two flipped to smudge it.
+fn pattern() -> impl Strategy<Value = Pattern> {
+    (2..=17usize, 2..=17usize)
+        .prop_flat_map(|(width, height)| {
+            (
+                vec(vec(any::<bool>(), width), height),
+                prop_oneof![Just(None), (1..height).prop_map(Some)],
+                prop_oneof![Just(None), (1..width).prop_map(Some)],
+                vec((0..width, 0..height), 0..=2),
+            )
+        })
+        .prop_map(|(mut cells, row, col, smudges)| {
+            if let Some(at) = row {
+                for y in at..cells.len().min(2 * at) {
+                    cells[y] = cells[2 * at - 1 - y].clone();
+                }
+            } else if let Some(at) = col {
+                for row in &mut cells {
+                    for x in at..row.len().min(2 * at) {
+                        row[x] = row[2 * at - 1 - x];
+                    }
+                }
+            }
+            for (x, y) in smudges {
+                cells[y][x] = !cells[y][x];
+            }
+            cells
+        })
+}
+
+fn render(patterns: &[Pattern]) -> String {
+    let patterns: Vec<String> = patterns
+        .iter()
+        .map(|cells| {
+            cells
+                .iter()
+                .map(|row| {
+                    let mut line: String = row.iter().map(|&c| if c { '#' } else { '.' }).collect();
+                    line.push('\n');
+                    line
+                })
+                .collect()
+        })
+        .collect();
+    patterns.join("\n")
+}
+
+fn transpose(cells: &Pattern) -> Pattern {
+    (0..cells[0].len())
+        .map(|x| cells.iter().map(|row| row[x]).collect())
+        .collect()
+}
+
+pub fn inputs() -> impl Strategy<Value = String> {
+    vec(pattern(), 1..8).prop_map(|patterns| render(&patterns))
+}
+
+proptest! {
+    /// Rows are searched first, so a pattern that only reflects about a column
+    /// reflects about the same row once transposed
+    #[test]
+    fn transposing_turns_columns_into_rows(cells in pattern()) {
+        let run = aoc::run::<Solution>(&render(std::slice::from_ref(&cells))).unwrap();
+        let transposed = aoc::run::<Solution>(&render(&[transpose(&cells)])).unwrap();
+        for (summary, transposed) in [
+            (run.part_one, transposed.part_one),
+            (run.part_two, transposed.part_two),
+        ] {
+            if let aoc::Answer::Int(column @ 1..=99) = summary {
+                prop_assert_eq!(transposed, aoc::Answer::Int(100 * column));
+            }
+        }
+    }
+}
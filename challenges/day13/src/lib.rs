@@ -1,4 +1,11 @@
+use aoc::safe;
 use arrayvec::ArrayVec;
+use nom::error::ErrorKind;
+
+#[cfg(test)]
+mod generate;
+#[cfg(test)]
+mod reference;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Block {
@@ -23,22 +30,37 @@ impl<'a> Block {
                 rows.push(current_row);
                 break;
             } else {
+                safe::ensure_at(col < cols.len(), input, col, ErrorKind::Count)?;
                 current_row |= ((b & 1) as u32) << col;
                 cols[col] |= ((b & 1) as u32) << rows.len();
             }
         }
+        // `solve` needs at least one row and one column
+        if safe::ENABLED && max_col == 0 {
+            // either the block starts with a blank line, or its first line never ends
+            return Err(if input.starts_with('\n') {
+                safe::error(input, ErrorKind::OneOf)
+            } else {
+                safe::error_at(input, input.len(), ErrorKind::CrLf)
+            });
+        }
         cols.truncate(max_col);
-        input = &input[max_col + 1..];
+        input = safe::skip(input, max_col + 1)?;
 
         while !input.is_empty() && input.as_bytes()[0] != b'\n' {
+            safe::ensure(!rows.is_full(), input, ErrorKind::Count)?;
             let mut current_row = 0;
             for col in 0..max_col {
-                let b = input.as_bytes()[col];
+                let b = safe::byte(input, col)?;
                 current_row |= ((b & 1) as u32) << col;
                 cols[col] |= ((b & 1) as u32) << rows.len();
             }
+            if safe::ENABLED {
+                let end = safe::byte(input, max_col)?;
+                safe::ensure_at(end == b'\n', input, max_col, ErrorKind::CrLf)?;
+            }
             rows.push(current_row);
-            input = &input[max_col + 1..];
+            input = safe::skip(input, max_col + 1)?;
         }
         input = input.get(1..).unwrap_or_default();
 
@@ -118,11 +140,11 @@ impl Block {
 }
 
 impl Solution {
-    fn part_one(self) -> impl std::fmt::Display {
+    fn part_one(self) -> impl aoc::IntoAnswer {
         self.0.into_iter().map(Block::solve::<0>).sum::<usize>()
     }
 
-    fn part_two(self) -> impl std::fmt::Display {
+    fn part_two(self) -> impl aoc::IntoAnswer {
         self.0.into_iter().map(Block::solve::<1>).sum::<usize>()
     }
 }
@@ -133,52 +155,20 @@ impl Solution {
 // }
 
 impl aoc::Challenge for Solution {
-    fn part_one(self) -> impl std::fmt::Display {
+    fn part_one(self) -> impl aoc::IntoAnswer {
         self.part_one()
     }
 
-    fn part_two(self) -> impl std::fmt::Display {
+    fn part_two(self) -> impl aoc::IntoAnswer {
         self.part_two()
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::Solution;
-    use aoc::Parser;
-
-    const INPUT: &str = "#.##..##.
-..#.##.#.
-##......#
-##......#
-..#.##.#.
-..##..##.
-#.#.##.#.
-
-#...##..#
-#....#..#
-..##..###
-#####.##.
-#####.##.
-..##..###
-#....#..#
-";
-
-    #[test]
-    fn parse() {
-        let output = Solution::must_parse(INPUT);
-        println!("{output:?}");
-    }
-
-    #[test]
-    fn part_one() {
-        let output = Solution::must_parse(INPUT);
-        assert_eq!(output.part_one().to_string(), "405");
-    }
-
-    #[test]
-    fn part_two() {
-        let output = Solution::must_parse(INPUT);
-        assert_eq!(output.part_two().to_string(), "400");
-    }
-}
+aoc::solution!(
+    Solution,
+    year = 2023,
+    day = 13,
+    title = "Point of Incidence"
+);
+aoc::example_tests!(Solution);
+aoc::differential_tests!(Solution, reference::Naive, inputs = generate::inputs);
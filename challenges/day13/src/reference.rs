@@ -0,0 +1,41 @@
+//! Compares the mirrored rows and columns of each pattern cell by cell.
+
+pub struct Naive;
+
+/// How many cells differ when the pattern is folded between row `at - 1` and row `at`
+fn smudges(rows: &[Vec<u8>], at: usize) -> usize {
+    rows[..at]
+        .iter()
+        .rev()
+        .zip(&rows[at..])
+        .map(|(a, b)| a.iter().zip(b).filter(|(a, b)| a != b).count())
+        .sum()
+}
+
+fn transpose(rows: &[Vec<u8>]) -> Vec<Vec<u8>> {
+    (0..rows[0].len())
+        .map(|x| rows.iter().map(|row| row[x]).collect())
+        .collect()
+}
+
+/// The summary of one pattern, taking the first fold with exactly `expected` smudges
+fn summarize(pattern: &str, expected: usize) -> usize {
+    let rows: Vec<Vec<u8>> = pattern.lines().map(|l| l.bytes().collect()).collect();
+    let cols = transpose(&rows);
+    if let Some(at) = (1..rows.len()).find(|&at| smudges(&rows, at) == expected) {
+        return 100 * at;
+    }
+    (1..cols.len())
+        .find(|&at| smudges(&cols, at) == expected)
+        .unwrap_or(0)
+}
+
+impl aoc::Reference for Naive {
+    fn part_one(input: &str) -> impl aoc::IntoAnswer {
+        input.split("\n\n").map(|p| summarize(p, 0)).sum::<usize>()
+    }
+
+    fn part_two(input: &str) -> impl aoc::IntoAnswer {
+        input.split("\n\n").map(|p| summarize(p, 1)).sum::<usize>()
+    }
+}
@@ -0,0 +1,59 @@
+//! Rectangular platforms of empty space, cube rocks and round rocks, every row the same
+//! length as the solution reads the grid by its first line.
+
+use proptest::{collection::vec, prelude::*};
+
+use crate::Solution;
+
+type Platform = Vec<Vec<u8>>;
+
+fn platform() -> impl Strategy<Value = Platform> {
+    (1..=12usize, 1..=12usize).prop_flat_map(|(width, height)| {
+        let rock = prop::sample::select(b".#O".as_slice());
+        vec(vec(rock, width), height)
+    })
+}
+
+fn render(platform: &Platform) -> String {
+    platform
+        .iter()
+        .map(|row| format!("{}\n", std::str::from_utf8(row).unwrap()))
+        .collect()
+}
+
+proptest! {
+    /// Rocks only roll north for part one, so the columns can come in any order
+    #[test]
+    fn mirroring_keeps_part_one(platform in platform()) {
+        let mut mirrored = platform.clone();
+        mirrored.iter_mut().for_each(|row| row.reverse());
+        prop_assert_eq!(
+            aoc::run_part_one::<Solution>(&render(&mirrored)).unwrap(),
+            aoc::run_part_one::<Solution>(&render(&platform)).unwrap()
+        );
+    }
+
+    /// Rolling the rocks north before the solution does leaves them where they'd stop anyway
+    #[test]
+    fn rolling_first_keeps_part_one(platform in platform()) {
+        let mut rolled = platform.clone();
+        for x in 0..rolled[0].len() {
+            let mut stop = 0;
+            for y in 0..rolled.len() {
+                match rolled[y][x] {
+                    b'#' => stop = y + 1,
+                    b'O' => {
+                        rolled[y][x] = b'.';
+                        rolled[stop][x] = b'O';
+                        stop += 1;
+                    }
+                    _ => {}
+                }
+            }
+        }
+        prop_assert_eq!(
+            aoc::run_part_one::<Solution>(&render(&rolled)).unwrap(),
+            aoc::run_part_one::<Solution>(&render(&platform)).unwrap()
+        );
+    }
+}
@@ -1,7 +1,12 @@
 use std::collections::hash_map::Entry;
 
+use aoc::safe;
+use nom::error::ErrorKind;
 use rustc_hash::FxHashMap;
 
+#[cfg(test)]
+mod generate;
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct Solution {
     rocks: Vec<Rock>,
@@ -21,9 +26,15 @@ enum Rock {
 
 impl<'a> aoc::Parser<'a> for Solution {
     fn parse(input: &'a str) -> nom::IResult<&'a str, Self> {
-        let width = input.find('\n').unwrap() + 1;
+        let width = match input.find('\n') {
+            Some(i) => i + 1,
+            None => return Err(safe::error_at(input, input.len(), ErrorKind::CrLf)),
+        };
+        safe::lines(input, width)?;
         let height = input.len() / width;
-        let rocks = unsafe { std::mem::transmute::<&[u8], &[Rock]>(input.as_bytes()) }.to_vec();
+        let rocks =
+            unsafe { safe::cast::<Rock>(input, |b| matches!(b, b'.' | b'#' | b'O' | b'\n'))? }
+                .to_vec();
 
         Ok((
             "",
@@ -114,12 +125,12 @@ impl Solution {
         vec
     }
 
-    fn part_one(mut self) -> impl std::fmt::Display {
+    fn part_one(mut self) -> impl aoc::IntoAnswer {
         self.north();
         self.north_weight()
     }
 
-    fn part_two(mut self) -> impl std::fmt::Display {
+    fn part_two(mut self) -> impl aoc::IntoAnswer {
         let mut cache = FxHashMap::with_capacity_and_hasher(256, Default::default());
         let mut i = 0;
         let (idx, len) = loop {
@@ -156,47 +167,19 @@ impl Solution {
 // }
 
 impl aoc::Challenge for Solution {
-    fn part_one(self) -> impl std::fmt::Display {
+    fn part_one(self) -> impl aoc::IntoAnswer {
         self.part_one()
     }
 
-    fn part_two(self) -> impl std::fmt::Display {
+    fn part_two(self) -> impl aoc::IntoAnswer {
         self.part_two()
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::Solution;
-    use aoc::Parser;
-
-    const INPUT: &str = "O....#....
-O.OO#....#
-.....##...
-OO.#O....O
-.O.....O#.
-O.#..O.#.#
-..O..#O..O
-.......O..
-#....###..
-#OO..#....
-";
-
-    #[test]
-    fn parse() {
-        let output = Solution::must_parse(INPUT);
-        println!("{output:?}");
-    }
-
-    #[test]
-    fn part_one() {
-        let output = Solution::must_parse(INPUT);
-        assert_eq!(output.part_one().to_string(), "136");
-    }
-
-    #[test]
-    fn part_two() {
-        let output = Solution::must_parse(INPUT);
-        assert_eq!(output.part_two().to_string(), "64");
-    }
-}
+aoc::solution!(
+    Solution,
+    year = 2023,
+    day = 14,
+    title = "Parabolic Reflector Dish"
+);
+aoc::example_tests!(Solution);
@@ -0,0 +1,58 @@
+//! Sequences of steps with short lowercase labels, from few enough labels that no box
+//! outgrows the solution's eight slots, and single digit focal lengths.
+
+use proptest::{collection::vec, prelude::*};
+
+use crate::Solution;
+
+/// A step, as its label and the focal length of the lens to put in, or `None` to take it out
+type Step = (String, Option<u8>);
+
+fn sequence() -> impl Strategy<Value = Vec<Step>> {
+    vec("[a-z]{1,6}", 1..=12).prop_flat_map(|labels| {
+        let step = (
+            prop::sample::select(labels),
+            prop::option::weighted(0.7, 1..=9u8),
+        );
+        vec(step, 1..50)
+    })
+}
+
+fn render(steps: &[Step]) -> String {
+    let steps: Vec<String> = steps
+        .iter()
+        .map(|(label, lens)| match lens {
+            Some(lens) => format!("{label}={lens}"),
+            None => format!("{label}-"),
+        })
+        .collect();
+    format!("{}\n", steps.join(","))
+}
+
+proptest! {
+    /// Part one hashes each step on its own, so a sequence adds up to its two halves
+    #[test]
+    fn part_one_adds_up(first in sequence(), second in sequence()) {
+        let both = [first.as_slice(), second.as_slice()].concat();
+        let aoc::Answer::Int(both) = aoc::run_part_one::<Solution>(&render(&both)).unwrap() else {
+            panic!("part one isn't a number");
+        };
+        let aoc::Answer::Int(first) = aoc::run_part_one::<Solution>(&render(&first)).unwrap()
+        else {
+            panic!("part one isn't a number");
+        };
+        let aoc::Answer::Int(second) = aoc::run_part_one::<Solution>(&render(&second)).unwrap()
+        else {
+            panic!("part one isn't a number");
+        };
+        prop_assert_eq!(both, first + second);
+    }
+
+    /// Taking out a lens that was never put in leaves the boxes as they were
+    #[test]
+    fn removing_a_missing_lens_keeps_part_two(mut steps in sequence()) {
+        let part_two = aoc::run_part_two::<Solution>(&render(&steps)).unwrap();
+        steps.push(("missing".to_owned(), None));
+        prop_assert_eq!(aoc::run_part_two::<Solution>(&render(&steps)).unwrap(), part_two);
+    }
+}
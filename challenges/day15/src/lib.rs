@@ -1,11 +1,30 @@
+use aoc::safe;
 use arrayvec::ArrayVec;
+use nom::error::ErrorKind;
+
+#[cfg(test)]
+mod generate;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Solution<'a>(&'a str);
 
 impl<'a> aoc::Parser<'a> for Solution<'a> {
     fn parse(input: &'a str) -> nom::IResult<&'a str, Self> {
-        Ok(("", Self(input.trim_end())))
+        let input = input.trim_end();
+        if safe::ENABLED {
+            // part two splits a one digit lens off the end of each step that isn't a removal
+            for step in input.split(',') {
+                match step.as_bytes() {
+                    [.., b'-'] => {}
+                    [_, .., b'=', lens] => {
+                        let at = step.len() - 1;
+                        safe::ensure_at(lens.is_ascii_digit(), step, at, ErrorKind::Digit)?
+                    }
+                    _ => return Err(safe::error_at(step, step.len(), ErrorKind::OneOf)),
+                }
+            }
+        }
+        Ok(("", Self(input)))
     }
 }
 
@@ -28,7 +47,7 @@ fn hash(b: &[u8]) -> u32 {
 }
 
 impl Solution<'_> {
-    fn part_one(self) -> impl std::fmt::Display {
+    fn part_one(self) -> impl aoc::IntoAnswer {
         let mut sum = 0u32;
         let mut hash = 0u32;
         for b in self.0.bytes() {
@@ -42,7 +61,7 @@ impl Solution<'_> {
         sum + (hash & 0xff)
     }
 
-    fn part_two(self) -> impl std::fmt::Display {
+    fn part_two(self) -> impl aoc::IntoAnswer {
         const BOX: ArrayVec<(&[u8], u8), 8> = ArrayVec::<(&[u8], u8), 8>::new_const();
         let mut boxes = [BOX; 256];
         let iter = memchr::memchr_iter(b',', self.0.as_bytes());
@@ -100,38 +119,19 @@ impl Solution<'_> {
 // }
 
 impl aoc::Challenge for Solution<'_> {
-    fn part_one(self) -> impl std::fmt::Display {
+    fn part_one(self) -> impl aoc::IntoAnswer {
         self.part_one()
     }
 
-    fn part_two(self) -> impl std::fmt::Display {
+    fn part_two(self) -> impl aoc::IntoAnswer {
         self.part_two()
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::Solution;
-    use aoc::Parser;
-
-    const INPUT: &str = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7
-";
-
-    #[test]
-    fn parse() {
-        let output = Solution::must_parse(INPUT);
-        println!("{output:?}");
-    }
-
-    #[test]
-    fn part_one() {
-        let output = Solution::must_parse(INPUT);
-        assert_eq!(output.part_one().to_string(), "1320");
-    }
-
-    #[test]
-    fn part_two() {
-        let output = Solution::must_parse(INPUT);
-        assert_eq!(output.part_two().to_string(), "145");
-    }
-}
+aoc::solution!(
+    Solution<'static>,
+    year = 2023,
+    day = 15,
+    title = "Lens Library"
+);
+aoc::example_tests!(Solution<'static>);
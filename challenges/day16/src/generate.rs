@@ -0,0 +1,52 @@
+//! Contraptions the size of the example, as the solution only knows that size and the real
+//! input's.
+
+use proptest::{collection::vec, prelude::*};
+
+use crate::Solution;
+
+type Contraption = Vec<Vec<u8>>;
+
+fn contraption() -> impl Strategy<Value = Contraption> {
+    vec(vec(prop::sample::select(b"....|-/\\".as_slice()), 10), 10)
+}
+
+fn render(contraption: &Contraption) -> String {
+    contraption
+        .iter()
+        .map(|row| format!("{}\n", std::str::from_utf8(row).unwrap()))
+        .collect()
+}
+
+proptest! {
+    /// Part two tries every way in, including the one part one takes
+    #[test]
+    fn part_two_is_the_most(contraption in contraption()) {
+        let run = aoc::run::<Solution>(&render(&contraption)).unwrap();
+        let (aoc::Answer::Int(one), aoc::Answer::Int(two)) = (run.part_one, run.part_two) else {
+            panic!("the parts aren't numbers");
+        };
+        prop_assert!(one <= two);
+    }
+
+    /// Mirrored left to right, with the mirrors turned to match, every beam still lights the
+    /// same number of tiles from the other side
+    #[test]
+    fn mirroring_keeps_part_two(contraption in contraption()) {
+        let mut mirrored = contraption.clone();
+        for row in &mut mirrored {
+            row.reverse();
+            for space in row {
+                *space = match *space {
+                    b'/' => b'\\',
+                    b'\\' => b'/',
+                    space => space,
+                };
+            }
+        }
+        prop_assert_eq!(
+            aoc::run_part_two::<Solution>(&render(&mirrored)).unwrap(),
+            aoc::run_part_two::<Solution>(&render(&contraption)).unwrap()
+        );
+    }
+}
@@ -1,6 +1,11 @@
+use aoc::safe;
 use arrayvec::ArrayVec;
+use nom::error::ErrorKind;
 use rayon::iter::{ParallelBridge, ParallelIterator};
 
+#[cfg(test)]
+mod generate;
+
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub struct Solution<'a> {
     widthd: u64,
@@ -91,12 +96,21 @@ impl Dir {
 
 impl<'a> aoc::Parser<'a> for Solution<'a> {
     fn parse(input: &'a str) -> nom::IResult<&'a str, Self> {
-        let data = unsafe { std::mem::transmute::<&[u8], &[Space]>(input.as_bytes()) };
+        let data = unsafe {
+            safe::cast::<Space>(input, |b| {
+                matches!(b, b'.' | b'|' | b'-' | b'/' | b'\\' | b'\n')
+            })?
+        };
         let (width, widthd, height) = if 110 * 111 == input.len() {
             (111, u64::MAX / 111 + 1, 110)
         } else {
             (11, u64::MAX / 11 + 1, 10)
         };
+        if safe::ENABLED && data.len() != (width * height) as usize {
+            // the length picked the grid, so go by the first line to find the one that's off
+            safe::lines(input, input.find('\n').map_or(input.len(), |i| i + 1))?;
+            return Err(safe::error(input, ErrorKind::LengthValue));
+        }
 
         Ok((
             "",
@@ -141,11 +155,11 @@ impl Solution<'_> {
         grid.into_iter().filter(|x| *x > 0).count()
     }
 
-    fn part_one(self) -> impl std::fmt::Display {
+    fn part_one(self) -> impl aoc::IntoAnswer {
         self.solve(0, Dir::East)
     }
 
-    fn part_two(self) -> impl std::fmt::Display {
+    fn part_two(self) -> impl aoc::IntoAnswer {
         let top = (0..self.width - 1).map(|pos| (pos, Dir::South));
         let bottom =
             (0..self.width - 1).map(|pos| ((self.height - 1) * self.width + pos, Dir::North));
@@ -168,47 +182,19 @@ impl Solution<'_> {
 // }
 
 impl aoc::Challenge for Solution<'_> {
-    fn part_one(self) -> impl std::fmt::Display {
+    fn part_one(self) -> impl aoc::IntoAnswer {
         self.part_one()
     }
 
-    fn part_two(self) -> impl std::fmt::Display {
+    fn part_two(self) -> impl aoc::IntoAnswer {
         self.part_two()
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::Solution;
-    use aoc::Parser;
-
-    const INPUT: &str = r#".|...\....
-|.-.\.....
-.....|-...
-........|.
-..........
-.........\
-..../.\\..
-.-.-/..|..
-.|....-|.\
-..//.|....
-"#;
-
-    #[test]
-    fn parse() {
-        let output = Solution::must_parse(INPUT);
-        println!("{output:?}");
-    }
-
-    #[test]
-    fn part_one() {
-        let output = Solution::must_parse(INPUT);
-        assert_eq!(output.part_one().to_string(), "46");
-    }
-
-    #[test]
-    fn part_two() {
-        let output = Solution::must_parse(INPUT);
-        assert_eq!(output.part_two().to_string(), "51");
-    }
-}
+aoc::solution!(
+    Solution<'static>,
+    year = 2023,
+    day = 16,
+    title = "The Floor Will Be Lava"
+);
+aoc::example_tests!(Solution<'static>);
@@ -0,0 +1,31 @@
+//! City blocks the size of the example, as the solution only knows that size and the real
+//! input's.
+
+use proptest::{collection::vec, prelude::*};
+
+use crate::Solution;
+
+type City = Vec<Vec<u8>>;
+
+fn city() -> impl Strategy<Value = City> {
+    vec(vec(b'1'..=b'9', 13), 13)
+}
+
+fn render(city: &City) -> String {
+    city.iter()
+        .map(|row| format!("{}\n", std::str::from_utf8(row).unwrap()))
+        .collect()
+}
+
+proptest! {
+    /// The crucibles go from one corner to the opposite one, so flipping the city about that
+    /// diagonal loses no heat either way
+    #[test]
+    fn transposing_keeps_the_heat_loss(city in city()) {
+        let transposed: City = (0..13).map(|x| city.iter().map(|row| row[x]).collect()).collect();
+        prop_assert_eq!(
+            aoc::run::<Solution>(&render(&transposed)).unwrap(),
+            aoc::run::<Solution>(&render(&city)).unwrap()
+        );
+    }
+}
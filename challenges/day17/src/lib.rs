@@ -1,8 +1,13 @@
 use core::panic;
 use std::collections::hash_map::Entry;
 
+use aoc::safe;
+use nom::error::ErrorKind;
 use rustc_hash::FxHashMap;
 
+#[cfg(test)]
+mod generate;
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct Solution<'a> {
     widthd: u32,
@@ -18,6 +23,24 @@ impl<'a> aoc::Parser<'a> for Solution<'a> {
         } else {
             (14, u32::MAX / 14 + 1, 13)
         };
+        if safe::ENABLED {
+            // the parts walk the grid assuming these dimensions, and that every cell is a digit
+            if let Some(i) = input
+                .bytes()
+                .position(|b| !b.is_ascii_digit() && b != b'\n')
+            {
+                return Err(safe::error_at(input, i, ErrorKind::Digit));
+            }
+            let size = width as usize * height as usize;
+            // the length picked the grid, so if it's off go by the first line instead
+            let line = if input.len() == size {
+                width as usize
+            } else {
+                input.find('\n').map_or(input.len(), |i| i + 1)
+            };
+            safe::lines(input, line)?;
+            safe::ensure(input.len() == size, input, ErrorKind::LengthValue)?;
+        }
 
         Ok((
             "",
@@ -163,11 +186,11 @@ impl Solution<'_> {
         panic!();
     }
 
-    fn part_one(self) -> impl std::fmt::Display {
+    fn part_one(self) -> impl aoc::IntoAnswer {
         self.solve::<1, 3, 8>()
     }
 
-    fn part_two(self) -> impl std::fmt::Display {
+    fn part_two(self) -> impl aoc::IntoAnswer {
         self.solve::<4, 10, 16>()
     }
 }
@@ -208,50 +231,19 @@ impl<T> BucketQueue<T> {
 // }
 
 impl aoc::Challenge for Solution<'_> {
-    fn part_one(self) -> impl std::fmt::Display {
+    fn part_one(self) -> impl aoc::IntoAnswer {
         self.part_one()
     }
 
-    fn part_two(self) -> impl std::fmt::Display {
+    fn part_two(self) -> impl aoc::IntoAnswer {
         self.part_two()
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::Solution;
-    use aoc::Parser;
-
-    const INPUT: &str = "2413432311323
-3215453535623
-3255245654254
-3446585845452
-4546657867536
-1438598798454
-4457876987766
-3637877979653
-4654967986887
-4564679986453
-1224686865563
-2546548887735
-4322674655533
-";
-
-    #[test]
-    fn parse() {
-        let output = Solution::must_parse(INPUT);
-        println!("{output:?}");
-    }
-
-    #[test]
-    fn part_one() {
-        let output = Solution::must_parse(INPUT);
-        assert_eq!(output.part_one().to_string(), "102");
-    }
-
-    #[test]
-    fn part_two() {
-        let output = Solution::must_parse(INPUT);
-        assert_eq!(output.part_two().to_string(), "94");
-    }
-}
+aoc::solution!(
+    Solution<'static>,
+    year = 2023,
+    day = 17,
+    title = "Clumsy Crucible"
+);
+aoc::example_tests!(Solution<'static>);
@@ -0,0 +1,93 @@
+//! Dig plans tracing simple loops, their colours spelling out the same plan turned.
+
+use proptest::{collection::vec, prelude::*, sample::Index};
+
+use crate::Solution;
+
+/// Runs of the plan, as a unit step and how far to dig
+type Plan = Vec<((i64, i64), i64)>;
+
+/// A dig plan around a shape made of columns, each a span of rows overlapping the last,
+/// which makes a simple loop.
+fn plan() -> impl Strategy<Value = Plan> {
+    vec((0..20i64, 1..20i64), 1..20).prop_map(|spans| {
+        let columns: Vec<(i64, i64)> = spans
+            .iter()
+            .scan(0, |last, &(offset, len)| {
+                // overlap the previous column by at least one row
+                let lo = *last - offset.min(len - 1);
+                *last = lo;
+                Some((lo, lo + len))
+            })
+            .collect();
+
+        // corners clockwise from the top left, in the plan's coordinates, where up is negative
+        let mut corners = vec![(0, columns[0].0)];
+        for (x, &(lo, _)) in columns.iter().enumerate() {
+            corners.push((x as i64, lo));
+            corners.push((x as i64 + 1, lo));
+        }
+        for (x, &(_, hi)) in columns.iter().enumerate().rev() {
+            corners.push((x as i64 + 1, hi));
+            corners.push((x as i64, hi));
+        }
+        corners.push((0, columns[0].0));
+
+        // steps between corners, merged into runs of the same direction
+        let mut runs: Plan = Vec::new();
+        for pair in corners.windows(2) {
+            let [(x0, y0), (x1, y1)] = [pair[0], pair[1]];
+            let dist = (x1 - x0).abs() + (y1 - y0).abs();
+            if dist == 0 {
+                continue;
+            }
+            let dir = ((x1 - x0).signum(), (y1 - y0).signum());
+            match runs.last_mut() {
+                Some((last, len)) if *last == dir => *len += dist,
+                _ => runs.push((dir, dist)),
+            }
+        }
+        if runs.len() > 1 && runs[0].0 == runs[runs.len() - 1].0 {
+            let (_, len) = runs.pop().unwrap();
+            runs[0].1 += len;
+        }
+        runs
+    })
+}
+
+/// Colours give the same plan turned a quarter clockwise
+fn render(plan: &Plan) -> String {
+    plan.iter()
+        .map(|&((dx, dy), dist)| {
+            let (dir, turned) = match (dx, dy) {
+                (1, 0) => ("R", 1),
+                (0, 1) => ("D", 2),
+                (-1, 0) => ("L", 3),
+                _ => ("U", 0),
+            };
+            format!("{dir} {dist} (#{dist:05x}{turned})\n")
+        })
+        .collect()
+}
+
+pub fn inputs() -> impl Strategy<Value = String> {
+    plan().prop_map(|plan| render(&plan))
+}
+
+proptest! {
+    /// The lagoon doesn't depend on which corner the plan starts from
+    #[test]
+    fn rotating_the_plan_keeps_the_area(mut plan in plan(), start in any::<Index>()) {
+        let run = aoc::run::<Solution>(&render(&plan)).unwrap();
+        let start = start.index(plan.len());
+        plan.rotate_left(start);
+        prop_assert_eq!(aoc::run::<Solution>(&render(&plan)).unwrap(), run);
+    }
+
+    /// Turning the plan doesn't change the lagoon's shape, and the colours are the plan turned
+    #[test]
+    fn turning_the_plan_keeps_the_area(plan in plan()) {
+        let run = aoc::run::<Solution>(&render(&plan)).unwrap();
+        prop_assert_eq!(run.part_one, run.part_two);
+    }
+}
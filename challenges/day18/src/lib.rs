@@ -1,3 +1,11 @@
+use aoc::safe;
+use nom::error::ErrorKind;
+
+#[cfg(test)]
+mod generate;
+#[cfg(test)]
+mod reference;
+
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum Dir {
     R,
@@ -74,28 +82,36 @@ fn dir2(x: u8) -> Dir {
 
 impl<'a> aoc::Parser<'a> for Solution {
     fn parse(input: &'a str) -> nom::IResult<&'a str, Self> {
-        let mut input = input.as_bytes();
+        let mut rest = input.as_bytes();
         let mut output = Vec::with_capacity(1024);
-        while !input.is_empty() {
-            let dir = dir1(input[0]);
-            let len = if input[13] == b'\n' { 14 } else { 15 };
+        while !rest.is_empty() {
+            // the accessors take `input` and an offset, as errors are located within the `str`
+            let at = input.len() - rest.len();
+            let dir = dir1(safe::byte(input, at)?);
+            let len = if safe::byte(input, at + 13)? == b'\n' {
+                14
+            } else {
+                15
+            };
             let dist = if len == 14 {
-                (input[2] & 0xf) as i64
+                (safe::byte(input, at + 2)? & 0xf) as i64
             } else {
-                ((input[2] & 0xf) * 10 + (input[3] & 0xf)) as i64
+                ((safe::byte(input, at + 2)? & 0xf) * 10 + (safe::byte(input, at + 3)? & 0xf))
+                    as i64
             };
             let line1 = Line { dir, dist };
 
-            let &[a, b, c, d, e, f] = &input[len - 8..len - 2] else {
-                panic!()
-            };
+            let [a, b, c, d, e, f] = safe::bytes_at(input, at + len - 8)?;
             let dist = hex(a) << 16 | hex(b) << 12 | hex(c) << 8 | hex(d) << 4 | hex(e);
             let dir = dir2(f);
             let line2 = Line { dir, dist };
 
             output.push((line1, line2));
-            input = &input[len..];
+            safe::ensure_at(len <= rest.len(), input, input.len(), ErrorKind::Eof)?;
+            rest = &rest[len..];
         }
+        // the parts start from the first instruction
+        safe::ensure(!output.is_empty(), input, ErrorKind::Eof)?;
 
         Ok(("", Self(output)))
     }
@@ -137,11 +153,11 @@ fn solve(mut inst: impl Iterator<Item = Line>) -> i64 {
 }
 
 impl Solution {
-    fn part_one(self) -> impl std::fmt::Display {
+    fn part_one(self) -> impl aoc::IntoAnswer {
         solve(self.0.into_iter().map(|l| l.0))
     }
 
-    fn part_two(self) -> impl std::fmt::Display {
+    fn part_two(self) -> impl aoc::IntoAnswer {
         solve(self.0.into_iter().map(|l| l.1))
     }
 }
@@ -152,51 +168,15 @@ impl Solution {
 // }
 
 impl aoc::Challenge for Solution {
-    fn part_one(self) -> impl std::fmt::Display {
+    fn part_one(self) -> impl aoc::IntoAnswer {
         self.part_one()
     }
 
-    fn part_two(self) -> impl std::fmt::Display {
+    fn part_two(self) -> impl aoc::IntoAnswer {
         self.part_two()
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::Solution;
-    use aoc::Parser;
-
-    const INPUT: &str = "R 6 (#70c710)
-D 5 (#0dc571)
-L 2 (#5713f0)
-D 2 (#d2c081)
-R 2 (#59c680)
-D 2 (#411b91)
-L 5 (#8ceee2)
-U 2 (#caa173)
-L 1 (#1b58a2)
-U 2 (#caa171)
-R 2 (#7807d2)
-U 3 (#a77fa3)
-L 2 (#015232)
-U 2 (#7a21e3)
-";
-
-    #[test]
-    fn parse() {
-        let output = Solution::must_parse(INPUT);
-        println!("{output:?}");
-    }
-
-    #[test]
-    fn part_one() {
-        let output = Solution::must_parse(INPUT);
-        assert_eq!(output.part_one().to_string(), "62");
-    }
-
-    #[test]
-    fn part_two() {
-        let output = Solution::must_parse(INPUT);
-        assert_eq!(output.part_two().to_string(), "952408144115");
-    }
-}
+aoc::solution!(Solution, year = 2023, day = 18, title = "Lavaduct Lagoon");
+aoc::example_tests!(Solution);
+aoc::differential_tests!(Solution, reference::Naive, inputs = generate::inputs);
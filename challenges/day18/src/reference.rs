@@ -0,0 +1,98 @@
+//! Digs the trench out on a grid and flood fills the outside, rather than using Pick's theorem.
+//! The grid is coordinate compressed, so that part two's huge distances still fit.
+
+use std::collections::VecDeque;
+
+pub struct Naive;
+
+fn step(dir: &str) -> (i64, i64) {
+    match dir {
+        "R" | "0" => (1, 0),
+        "D" | "1" => (0, 1),
+        "L" | "2" => (-1, 0),
+        "U" | "3" => (0, -1),
+        _ => panic!("unknown direction {dir}"),
+    }
+}
+
+/// The cells dug out by following the plan, trench and interior both
+fn lagoon(plan: impl Iterator<Item = ((i64, i64), i64)>) -> i64 {
+    let mut trench = vec![(0, 0)];
+    for ((dx, dy), dist) in plan {
+        let (x, y) = trench[trench.len() - 1];
+        trench.push((x + dx * dist, y + dy * dist));
+    }
+
+    // every cell between two consecutive breaks is alike, the trench running through
+    // single cell wide rows and columns, with a spare break on each side for the outside
+    let breaks = |coord: fn(&(i64, i64)) -> i64| {
+        let mut breaks: Vec<i64> = trench.iter().map(coord).flat_map(|c| [c, c + 1]).collect();
+        let (min, max) = (
+            breaks.iter().min().unwrap() - 1,
+            breaks.iter().max().unwrap() + 1,
+        );
+        breaks.extend([min, max]);
+        breaks.sort_unstable();
+        breaks.dedup();
+        breaks
+    };
+    let (xs, ys) = (breaks(|p| p.0), breaks(|p| p.1));
+    let index = |breaks: &[i64], c: i64| breaks.binary_search(&c).unwrap();
+
+    let (width, height) = (xs.len() - 1, ys.len() - 1);
+    let mut dug = vec![false; width * height];
+    for pair in trench.windows(2) {
+        let [(x0, y0), (x1, y1)] = [pair[0], pair[1]];
+        for x in index(&xs, x0.min(x1))..=index(&xs, x0.max(x1)) {
+            for y in index(&ys, y0.min(y1))..=index(&ys, y0.max(y1)) {
+                dug[y * width + x] = true;
+            }
+        }
+    }
+
+    let mut outside = vec![false; width * height];
+    let mut queue = VecDeque::from([(0usize, 0usize)]);
+    outside[0] = true;
+    while let Some((x, y)) = queue.pop_front() {
+        let neighbours = [
+            (x.wrapping_sub(1), y),
+            (x + 1, y),
+            (x, y.wrapping_sub(1)),
+            (x, y + 1),
+        ];
+        for (x, y) in neighbours {
+            if x < width && y < height && !dug[y * width + x] && !outside[y * width + x] {
+                outside[y * width + x] = true;
+                queue.push_back((x, y));
+            }
+        }
+    }
+
+    let mut area = 0;
+    for y in 0..height {
+        for x in 0..width {
+            if !outside[y * width + x] {
+                area += (xs[x + 1] - xs[x]) * (ys[y + 1] - ys[y]);
+            }
+        }
+    }
+    area
+}
+
+impl aoc::Reference for Naive {
+    fn part_one(input: &str) -> impl aoc::IntoAnswer {
+        lagoon(input.lines().map(|line| {
+            let mut words = line.split_whitespace();
+            let dir = step(words.next().unwrap());
+            (dir, words.next().unwrap().parse().unwrap())
+        }))
+    }
+
+    fn part_two(input: &str) -> impl aoc::IntoAnswer {
+        lagoon(input.lines().map(|line| {
+            let (_, colour) = line.split_once('#').unwrap();
+            let dist = i64::from_str_radix(&colour[..5], 16).unwrap();
+            (step(&colour[5..6]), dist)
+        }))
+    }
+}
@@ -0,0 +1,110 @@
+//! Workflows that only ever send parts on to a new workflow, so that none of them loop,
+//! and parts rated 1 to 4000 like the puzzle's.
+
+use proptest::{collection::vec, prelude::*};
+
+use crate::Solution;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Target {
+    Accept,
+    Reject,
+    /// The next workflow still to be given out
+    Next,
+}
+
+/// A condition on one rating, and where it sends the part
+type Rule = (u8, u8, u32, Target);
+
+fn target() -> impl Strategy<Value = Target> {
+    prop_oneof![
+        Just(Target::Accept),
+        Just(Target::Reject),
+        Just(Target::Next)
+    ]
+}
+
+fn rule() -> impl Strategy<Value = Rule> {
+    (
+        prop::sample::select(b"xmas".to_vec()),
+        prop::sample::select(b"<>".to_vec()),
+        1..=4000u32,
+        target(),
+    )
+}
+
+/// Workflows as rules and a fallback. Whenever a workflow sends parts on,
+/// it's to a new one, so that they form a tree rooted at `in`.
+fn workflows() -> impl Strategy<Value = Vec<(Vec<Rule>, Target)>> {
+    vec((vec(rule(), 0..=4), target()), 1..30)
+}
+
+fn parts() -> impl Strategy<Value = Vec<[u32; 4]>> {
+    vec([1..=4000u32, 1..=4000, 1..=4000, 1..=4000], 1..50)
+}
+
+/// Names of two or three letters, none of them `in`
+fn name(i: usize) -> String {
+    let letter = |n: usize| (b'a' + (n % 26) as u8) as char;
+    match i {
+        0 => "in".to_owned(),
+        _ if i.is_multiple_of(2) => [letter(i), letter(i / 26 + 1)].iter().collect(),
+        _ => [letter(i), letter(i / 26), 'q'].iter().collect(),
+    }
+}
+
+/// Renders the system, sending accepted parts to `accept` and rejected ones to `reject`
+fn render(
+    workflows: &[(Vec<Rule>, Target)],
+    parts: &[[u32; 4]],
+    accept: &str,
+    reject: &str,
+) -> String {
+    let mut given = 0;
+    let mut to = |target: Target| match target {
+        Target::Accept => accept.to_owned(),
+        Target::Reject => reject.to_owned(),
+        // run out of workflows to send to, so stop here
+        Target::Next if given + 1 == workflows.len() => reject.to_owned(),
+        Target::Next => {
+            given += 1;
+            name(given)
+        }
+    };
+
+    let mut input = String::new();
+    for (i, (rules, fallback)) in workflows.iter().enumerate() {
+        input += &name(i);
+        input.push('{');
+        for &(xmas, op, val, target) in rules {
+            input += &format!("{}{}{val}:{},", xmas as char, op as char, to(target));
+        }
+        input += &to(*fallback);
+        input += "}\n";
+    }
+    input.push('\n');
+    for [x, m, a, s] in parts {
+        input += &format!("{{x={x},m={m},a={a},s={s}}}\n");
+    }
+    input
+}
+
+proptest! {
+    /// Every part, and every combination of ratings, is either accepted or rejected
+    #[test]
+    fn swapping_accept_and_reject_complements(workflows in workflows(), parts in parts()) {
+        let run = aoc::run::<Solution>(&render(&workflows, &parts, "A", "R")).unwrap();
+        let swapped = aoc::run::<Solution>(&render(&workflows, &parts, "R", "A")).unwrap();
+
+        let total: u32 = parts.iter().flatten().sum();
+        let (aoc::Answer::Int(accepted), aoc::Answer::Int(rejected)) = (run.part_one, swapped.part_one) else {
+            panic!("part one isn't a number");
+        };
+        prop_assert_eq!(accepted + rejected, total as i128);
+
+        let (aoc::Answer::Int(accepted), aoc::Answer::Int(rejected)) = (run.part_two, swapped.part_two) else {
+            panic!("part two isn't a number");
+        };
+        prop_assert_eq!(accepted + rejected, 4000i128.pow(4));
+    }
+}
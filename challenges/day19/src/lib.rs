@@ -1,6 +1,11 @@
 use std::{collections::HashMap, hash::BuildHasherDefault};
 
+use aoc::safe;
 use arrayvec::ArrayVec;
+use nom::error::ErrorKind;
+
+#[cfg(test)]
+mod generate;
 
 type WorkflowName = u32;
 
@@ -49,7 +54,8 @@ impl From<std::ops::RangeInclusive<u32>> for RangeInclusive {
 
 impl RangeInclusive {
     fn len(self) -> u64 {
-        (self.end - self.start + 1) as u64
+        // splitting on a rule can leave a range that ends well before it starts
+        (self.end + 1).saturating_sub(self.start) as u64
     }
 }
 
@@ -77,17 +83,17 @@ enum Outcome {
 impl<'a> aoc::Parser<'a> for Solution {
     fn parse(mut input: &'a str) -> nom::IResult<&'a str, Self> {
         let mut workflows = HashMap::with_capacity_and_hasher(600, Default::default());
-        while input.as_bytes()[0] != b'\n' {
-            let workflow = match input.as_bytes()[0..4] {
+        while safe::byte(input, 0)? != b'\n' {
+            let workflow = match safe::bytes(input)? {
                 [a, b, c, b'{'] => {
-                    input = unsafe { input.get_unchecked(4..) };
+                    input = unsafe { safe::skip_unchecked(input, 4)? };
                     u32::from_ne_bytes([a, b, c, 0])
                 }
                 [a, b, b'{', _] => {
-                    input = unsafe { input.get_unchecked(3..) };
+                    input = unsafe { safe::skip_unchecked(input, 3)? };
                     u32::from_ne_bytes([a, b, 0, 0])
                 }
-                _ => unreachable!(),
+                _ => return Err(safe::error_at(input, 3, ErrorKind::OneOf)),
             };
             let rules;
             (input, rules) = Rules::parse(input)?;
@@ -96,7 +102,7 @@ impl<'a> aoc::Parser<'a> for Solution {
 
         let mut parts = Vec::with_capacity(100);
         // skip newline separator
-        input = &input[1..];
+        input = safe::skip(input, 1)?;
         while !input.is_empty() {
             let part;
             (input, part) = Part::parse(input)?;
@@ -107,43 +113,54 @@ impl<'a> aoc::Parser<'a> for Solution {
     }
 }
 
+/// Reads digits up to `end`, skipping past it
+fn number(mut input: &str, end: u8) -> nom::IResult<&str, u32> {
+    let mut n = 0;
+    loop {
+        match safe::byte(input, 0)? {
+            b if b == end => break,
+            v => {
+                safe::ensure(v.is_ascii_digit(), input, ErrorKind::Digit)?;
+                safe::ensure(n <= (u32::MAX - 9) / 10, input, ErrorKind::TooLarge)?;
+                n = n * 10 + (v & 0xf) as u32
+            }
+        }
+        input = safe::skip(input, 1)?;
+    }
+    Ok((safe::skip(input, 1)?, n))
+}
+
 impl<'a> Rules {
     fn parse(mut input: &'a str) -> nom::IResult<&'a str, Self> {
         // s<537:gd,x>2440:R,A}
 
         let mut rules = ArrayVec::new();
-        while input.as_bytes()[1] == b'<' || input.as_bytes()[1] == b'>' {
-            let xmas = input.as_bytes()[0];
-            let op = input.as_bytes()[1];
-            input = &input[2..];
-            let mut val = 0;
-            loop {
-                match input.as_bytes()[0] {
-                    b':' => break,
-                    v => val = val * 10 + (v & 0xf) as u32,
-                }
-                input = &input[1..];
-            }
-            input = &input[1..];
-            let outcome = match input.as_bytes()[0] {
+        while matches!(safe::byte(input, 1)?, b'<' | b'>') {
+            let xmas = safe::byte(input, 0)?;
+            let op = safe::byte(input, 1)?;
+            safe::ensure(b"xmas".contains(&xmas), input, ErrorKind::OneOf)?;
+            safe::ensure(!rules.is_full(), input, ErrorKind::Count)?;
+            let val;
+            (input, val) = number(safe::skip(input, 2)?, b':')?;
+            let outcome = match safe::byte(input, 0)? {
                 b'A' => {
-                    input = unsafe { input.get_unchecked(2..) };
+                    input = unsafe { safe::skip_unchecked(input, 2)? };
                     Outcome::Accept
                 }
                 b'R' => {
-                    input = unsafe { input.get_unchecked(2..) };
+                    input = unsafe { safe::skip_unchecked(input, 2)? };
                     Outcome::Reject
                 }
-                _ => Outcome::Move(match input.as_bytes()[0..4] {
+                _ => Outcome::Move(match safe::bytes(input)? {
                     [a, b, c, b','] => {
-                        input = unsafe { input.get_unchecked(4..) };
+                        input = unsafe { safe::skip_unchecked(input, 4)? };
                         u32::from_ne_bytes([a, b, c, 0])
                     }
                     [a, b, b',', _] => {
-                        input = unsafe { input.get_unchecked(3..) };
+                        input = unsafe { safe::skip_unchecked(input, 3)? };
                         u32::from_ne_bytes([a, b, 0, 0])
                     }
-                    _ => unreachable!(),
+                    _ => return Err(safe::error_at(input, 3, ErrorKind::OneOf)),
                 }),
             };
 
@@ -155,25 +172,25 @@ impl<'a> Rules {
             })
         }
 
-        let fallback = match input.as_bytes()[0] {
+        let fallback = match safe::byte(input, 0)? {
             b'A' => {
-                input = unsafe { input.get_unchecked(3..) };
+                input = unsafe { safe::skip_unchecked(input, 3)? };
                 Outcome::Accept
             }
             b'R' => {
-                input = unsafe { input.get_unchecked(3..) };
+                input = unsafe { safe::skip_unchecked(input, 3)? };
                 Outcome::Reject
             }
-            _ => Outcome::Move(match input.as_bytes()[0..4] {
+            _ => Outcome::Move(match safe::bytes(input)? {
                 [a, b, c, b'}'] => {
-                    input = unsafe { input.get_unchecked(5..) };
+                    input = unsafe { safe::skip_unchecked(input, 5)? };
                     u32::from_ne_bytes([a, b, c, 0])
                 }
                 [a, b, b'}', _] => {
-                    input = unsafe { input.get_unchecked(4..) };
+                    input = unsafe { safe::skip_unchecked(input, 4)? };
                     u32::from_ne_bytes([a, b, 0, 0])
                 }
-                _ => unreachable!(),
+                _ => return Err(safe::error_at(input, 3, ErrorKind::OneOf)),
             }),
         };
 
@@ -182,47 +199,15 @@ impl<'a> Rules {
 }
 
 impl<'a> Part {
-    fn parse(mut input: &'a str) -> nom::IResult<&'a str, Self> {
+    fn parse(input: &'a str) -> nom::IResult<&'a str, Self> {
         // let [x, m, a, s, input] = split_many(input, ["{x=", ",m=", ",a=", ",s=", "}\n"]).unwrap();
         //{x=787,m=2655,a=1222,s=2876}
 
-        input = &input[3..];
-        let mut x = 0;
-        loop {
-            match input.as_bytes()[0] {
-                b',' => break,
-                v => x = x * 10 + (v & 0xf) as u32,
-            }
-            input = &input[1..];
-        }
-        input = &input[3..];
-        let mut m = 0;
-        loop {
-            match input.as_bytes()[0] {
-                b',' => break,
-                v => m = m * 10 + (v & 0xf) as u32,
-            }
-            input = &input[1..];
-        }
-        input = &input[3..];
-        let mut a = 0;
-        loop {
-            match input.as_bytes()[0] {
-                b',' => break,
-                v => a = a * 10 + (v & 0xf) as u32,
-            }
-            input = &input[1..];
-        }
-        input = &input[3..];
-        let mut s = 0;
-        loop {
-            match input.as_bytes()[0] {
-                b'}' => break,
-                v => s = s * 10 + (v & 0xf) as u32,
-            }
-            input = &input[1..];
-        }
-        input = &input[2..];
+        let (input, x) = number(safe::skip(input, 3)?, b',')?;
+        let (input, m) = number(safe::skip(input, 2)?, b',')?;
+        let (input, a) = number(safe::skip(input, 2)?, b',')?;
+        let (input, s) = number(safe::skip(input, 2)?, b'}')?;
+        let input = safe::skip(input, 1)?;
 
         Ok((input, Self { x, m, a, s }))
     }
@@ -335,11 +320,11 @@ impl Part {
 }
 
 impl Solution {
-    fn part_one(self) -> impl std::fmt::Display {
+    fn part_one(&self) -> impl aoc::IntoAnswer {
         let in_workflow = u32::from_ne_bytes(*b"in\0\0");
 
         let mut sum = 0;
-        for part in self.parts {
+        for &part in &self.parts {
             let mut workflow = in_workflow;
             loop {
                 match self.workflows[&workflow].apply(part) {
@@ -355,7 +340,7 @@ impl Solution {
         sum
     }
 
-    fn part_two(self) -> impl std::fmt::Display {
+    fn part_two(&self) -> impl aoc::IntoAnswer {
         let mut dfs = Vec::new();
 
         dfs.push((
@@ -400,55 +385,30 @@ impl Solution {
 //     Solution::parse(input).unwrap().1.part_two()
 // }
 
-impl aoc::Challenge for Solution {
-    fn part_one(self) -> impl std::fmt::Display {
+impl aoc::ChallengeRef for Solution {
+    fn part_one(&self) -> impl aoc::IntoAnswer {
         self.part_one()
     }
 
-    fn part_two(self) -> impl std::fmt::Display {
+    fn part_two(&self) -> impl aoc::IntoAnswer {
         self.part_two()
     }
 }
 
+aoc::solution!(Solution, year = 2023, day = 19, title = "Aplenty");
+aoc::example_tests!(Solution);
+
 #[cfg(test)]
 mod tests {
-    use super::Solution;
-    use aoc::Parser;
-
-    const INPUT: &str = "px{a<2006:qkq,m>2090:A,rfg}
-pv{a>1716:R,A}
-lnx{m>1548:A,A}
-rfg{s<537:gd,x>2440:R,A}
-qs{s>3448:A,lnx}
-qkq{x<1416:A,crn}
-crn{x>2662:A,R}
-in{s<1351:px,qqz}
-qqz{s>2770:qs,m<1801:hdj,R}
-gd{a>3333:R,R}
-hdj{m>838:A,pv}
-
-{x=787,m=2655,a=1222,s=2876}
-{x=1679,m=44,a=2067,s=496}
-{x=2036,m=264,a=79,s=2244}
-{x=2461,m=1339,a=466,s=291}
-{x=2127,m=1623,a=2188,s=1013}
-";
-
-    #[test]
-    fn parse() {
-        let output = Solution::must_parse(INPUT);
-        println!("{output:?}");
-    }
-
-    #[test]
-    fn part_one() {
-        let output = Solution::must_parse(INPUT);
-        assert_eq!(output.part_one().to_string(), "19114");
-    }
+    use super::RangeInclusive;
 
     #[test]
-    fn part_two() {
-        let output = Solution::must_parse(INPUT);
-        assert_eq!(output.part_two().to_string(), "167409079868000");
+    fn empty_range() {
+        assert_eq!(RangeInclusive::from(1..=4000).len(), 4000);
+        assert_eq!(RangeInclusive::from(7..=7).len(), 1);
+        // `x>3000` on a range that stops at 2000 leaves 3001..=2000
+        let empty = |start, end| RangeInclusive { start, end }.len();
+        assert_eq!(empty(2001, 2000), 0);
+        assert_eq!(empty(3001, 2000), 0);
     }
 }
@@ -0,0 +1,91 @@
+//! Networks in the shape of the real input, four binary counters feeding `kl` and then `rx`,
+//! which is what part two counts on.
+
+use proptest::prelude::*;
+
+use crate::Solution;
+
+/// The conjunctions the solution watches, each inverting one counter into `kl`
+const GOALS: [&str; 4] = ["mk", "fp", "xt", "zc"];
+
+/// Names of two letters, none of them taken by the goals, `kl` or `rx`
+fn names() -> impl Iterator<Item = String> {
+    let letters = b'a'..=b'z';
+    letters
+        .clone()
+        .flat_map(move |a| {
+            letters
+                .clone()
+                .map(move |b| [a as char, b as char].iter().collect())
+        })
+        .filter(|name: &String| !GOALS.contains(&name.as_str()) && name != "kl" && name != "rx")
+}
+
+/// Four counters in the shape of the real input, each given by the number it counts up to.
+/// A chain of flip-flops holds the count in binary, and its conjunction resets it
+/// once it reaches the number, pulsing low to the counter's goal.
+fn counters() -> impl Strategy<Value = [u32; 4]> {
+    let counter = (4..=8u32).prop_flat_map(|bits| {
+        // the lowest and highest bits are always set
+        (0..1u32 << (bits - 2)).prop_map(move |middle| 1 | middle << 1 | 1 << (bits - 1))
+    });
+    [counter.clone(), counter.clone(), counter.clone(), counter]
+}
+
+/// The network's modules, one line each
+fn modules(counters: &[u32; 4]) -> Vec<String> {
+    let mut names = names();
+    let mut lines = vec!["&kl -> rx".to_owned()];
+    let mut starts = vec![];
+    for (&count, goal) in counters.iter().zip(GOALS) {
+        let bits = 32 - count.leading_zeros() as usize;
+        let flip_flops: Vec<String> = names.by_ref().take(bits).collect();
+        let hub = names.next().unwrap();
+
+        let mut resets = vec![goal.to_owned()];
+        for (i, flip_flop) in flip_flops.iter().enumerate() {
+            let mut to = flip_flops
+                .get(i + 1)
+                .cloned()
+                .into_iter()
+                .collect::<Vec<_>>();
+            if count & 1 << i != 0 {
+                to.push(hub.clone());
+            }
+            if i == 0 || count & 1 << i == 0 {
+                resets.push(flip_flop.clone());
+            }
+            lines.push(format!("%{flip_flop} -> {}", to.join(", ")));
+        }
+        lines.push(format!("&{hub} -> {}", resets.join(", ")));
+        lines.push(format!("&{goal} -> kl"));
+        starts.push(flip_flops[0].clone());
+    }
+    lines.push(format!("broadcaster -> {}", starts.join(", ")));
+    lines
+}
+
+fn render(modules: &[String]) -> String {
+    modules.iter().map(|line| format!("{line}\n")).collect()
+}
+
+proptest! {
+    /// The button has to be pressed until every counter resets at once
+    #[test]
+    fn counters_reset_together(counters in counters()) {
+        let run = aoc::run::<Solution>(&render(&modules(&counters))).unwrap();
+        let presses: u64 = counters.iter().map(|&c| c as u64).product();
+        prop_assert_eq!(run.part_two, aoc::Answer::Int(presses as i128));
+    }
+
+    /// Modules can be listed in any order
+    #[test]
+    fn shuffling_keeps_the_pulses(
+        (counters, shuffled) in counters().prop_flat_map(|counters| {
+            (Just(counters), Just(modules(&counters)).prop_shuffle())
+        })
+    ) {
+        let run = aoc::run::<Solution>(&render(&modules(&counters))).unwrap();
+        prop_assert_eq!(aoc::run::<Solution>(&render(&shuffled)).unwrap(), run);
+    }
+}
@@ -1,6 +1,8 @@
 use std::collections::VecDeque;
 
+use aoc::safe;
 use arrayvec::ArrayVec;
+use nom::error::ErrorKind;
 use petgraph::{
     dot::Dot,
     graph::NodeIndex,
@@ -10,6 +12,9 @@ use petgraph::{
 };
 use rustc_hash::FxHashMap;
 
+#[cfg(test)]
+mod generate;
+
 #[derive(Debug, PartialEq, Clone)]
 enum Type {
     /// on low pulse -> flip and send current value
@@ -51,18 +56,52 @@ pub struct Solution {
     graph: Graph<Type2, u8>,
 }
 
-fn parse_members(mut input: &str) -> nom::IResult<&str, ArrayVec<u16, 8>> {
+/// Reads a module's name as its id.
+///
+/// The real input only has names of two letters, which are their own id. Any other name,
+/// like the examples' `inv`, is numbered by its place in `others`. Those start at 1, as 0 is
+/// the broadcaster, and stay well below the smallest two letter id.
+fn name<'a>(input: &'a str, others: &mut Vec<&'a str>) -> nom::IResult<&'a str, u16> {
+    let len = input
+        .bytes()
+        .position(|b| !b.is_ascii_lowercase())
+        .unwrap_or(input.len());
+    let (name, input) = input.split_at(len);
+    let id = match *name.as_bytes() {
+        [a, b] => u16::from_ne_bytes([a, b]),
+        _ => {
+            safe::ensure(!name.is_empty(), input, ErrorKind::Alpha)?;
+            let i = match others.iter().position(|&other| other == name) {
+                Some(i) => i,
+                None => {
+                    safe::ensure(others.len() < 255, name, ErrorKind::Count)?;
+                    others.push(name);
+                    others.len() - 1
+                }
+            };
+            i as u16 + 1
+        }
+    };
+    Ok((input, id))
+}
+
+fn parse_members<'a>(
+    mut input: &'a str,
+    others: &mut Vec<&'a str>,
+) -> nom::IResult<&'a str, ArrayVec<u16, 8>> {
     let mut members = ArrayVec::new();
     loop {
-        let id = u16::from_ne_bytes([input.as_bytes()[0], input.as_bytes()[1]]);
+        safe::ensure(!members.is_full(), input, ErrorKind::Count)?;
+        let id;
+        (input, id) = name(input, others)?;
         members.push(id);
-        match input.as_bytes()[2] {
+        match safe::byte(input, 0)? {
             b'\n' => {
-                input = &input[3..];
+                input = safe::skip(input, 1)?;
                 break;
             }
             _ => {
-                input = &input[4..];
+                input = safe::skip(input, 2)?;
             }
         }
     }
@@ -76,14 +115,20 @@ impl<'a> aoc::Parser<'a> for Solution {
         let mut map = FxHashMap::with_capacity_and_hasher(60, Default::default());
         let mut map1 = FxHashMap::with_capacity_and_hasher(60, Default::default());
         let mut graph = Graph::new();
+        let mut others = Vec::new();
+        // where each module is declared, to point errors found after parsing at its line
+        let mut lines = FxHashMap::default();
 
         while !input.is_empty() {
-            let (typ, typ2) = match input.as_bytes()[0] {
+            let line = input;
+            let (typ, typ2) = match safe::byte(input, 0)? {
                 b'%' => (Type::FlipFlop(ArrayVec::new(), false), Type2::FlipFlop(0)),
                 b'&' => (Type::Conjunction(FxHashMap::default()), Type2::Conjunction),
                 _ => {
                     // broadcaster
-                    (input, broadcaster) = parse_members(&input[15..])?;
+                    let valid = input.starts_with("broadcaster -> ");
+                    safe::ensure(valid, input, ErrorKind::OneOf)?;
+                    (input, broadcaster) = parse_members(safe::skip(input, 15)?, &mut others)?;
 
                     broadcaster1 = graph.add_node(Type2::Button);
                     for &b in &broadcaster {
@@ -94,9 +139,9 @@ impl<'a> aoc::Parser<'a> for Solution {
                     continue;
                 }
             };
-            let id = u16::from_ne_bytes([input.as_bytes()[1], input.as_bytes()[2]]);
+            let (rest, id) = name(safe::skip(input, 1)?, &mut others)?;
             let members;
-            (input, members) = parse_members(&input[7..])?;
+            (input, members) = parse_members(safe::skip(rest, 4)?, &mut others)?;
 
             let node = *map1.entry(id).or_insert_with(|| graph.add_node(Type2::End));
             *graph.node_weight_mut(node).unwrap() = typ2;
@@ -105,6 +150,9 @@ impl<'a> aoc::Parser<'a> for Solution {
                 graph.add_edge(node, *node2, 255u8);
             }
 
+            if safe::ENABLED {
+                lines.insert(id, line);
+            }
             map.insert(id, Module { typ, members });
         }
 
@@ -124,7 +172,13 @@ impl<'a> aoc::Parser<'a> for Solution {
                     continue;
                 };
                 match &mut this.typ {
-                    Type::FlipFlop(m, _) => m.push(id),
+                    Type::FlipFlop(m, _) => {
+                        if safe::ENABLED && m.is_full() {
+                            let line = lines.get(&id).copied().unwrap_or(input);
+                            return Err(safe::error(line, ErrorKind::Count));
+                        }
+                        m.push(id)
+                    }
                     Type::Conjunction(m) => {
                         m.insert(id, false);
                     }
@@ -148,7 +202,7 @@ impl<'a> aoc::Parser<'a> for Solution {
 }
 
 impl Solution {
-    fn part_one(mut self) -> impl std::fmt::Display {
+    fn part_one(mut self) -> impl aoc::IntoAnswer {
         let mut commands = VecDeque::new();
         let mut pulses = [0; 2];
 
@@ -223,7 +277,7 @@ impl Solution {
         (0, 0)
     }
 
-    fn part_two(mut self) -> impl std::fmt::Display {
+    fn part_two(mut self) -> impl aoc::IntoAnswer {
         let mut commands = VecDeque::new();
         let mut goals = FxHashMap::from_iter([
             (u16::from_ne_bytes(*b"mk"), None::<u64>),
@@ -296,53 +350,14 @@ impl Solution {
 // }
 
 impl aoc::Challenge for Solution {
-    fn part_one(self) -> impl std::fmt::Display {
+    fn part_one(self) -> impl aoc::IntoAnswer {
         self.part_one()
     }
 
-    fn part_two(self) -> impl std::fmt::Display {
+    fn part_two(self) -> impl aoc::IntoAnswer {
         self.part_two()
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::Solution;
-    use aoc::Parser;
-    use petgraph::dot::Dot;
-
-    const INPUT1: &str = "broadcaster -> aa, bb, cc
-%aa -> bb
-%bb -> cc
-%cc -> in
-&in -> aa
-";
-
-    const INPUT2: &str = "broadcaster -> aa
-%aa -> in, co
-&in -> bb
-%bb -> co
-&co -> rx
-";
-
-    #[test]
-    fn parse() {
-        let output = Solution::must_parse(INPUT2);
-        println!("{output:?}");
-        println!("{:?}", Dot::new(&output.graph));
-    }
-
-    #[test]
-    fn part_one() {
-        let output = Solution::must_parse(INPUT1);
-        assert_eq!(output.part_one().to_string(), "32000000");
-        let output = Solution::must_parse(INPUT2);
-        assert_eq!(output.part_one().to_string(), "11687500");
-    }
-
-    // #[test]
-    // fn part_two() {
-    //     let output = Solution::must_parse(INPUT2);
-    //     assert_eq!(output.part_two().to_string(), "0");
-    // }
-}
+aoc::solution!(Solution, year = 2023, day = 20, title = "Pulse Propagation");
+aoc::example_tests!(Solution, part_one);
@@ -0,0 +1,13 @@
+//! Seeds each fuzz target's corpus with the examples from its day's README.
+//!
+//! ```sh
+//! cargo run --manifest-path fuzz/Cargo.toml --example seed_corpus
+//! ```
+
+fn main() -> std::io::Result<()> {
+    for (name, dir) in fuzz::days()? {
+        let seeds = fuzz::seed(&name, &dir)?;
+        println!("{name}: {seeds} seeds");
+    }
+    Ok(())
+}
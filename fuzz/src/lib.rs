@@ -0,0 +1,49 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Parses arbitrary bytes as a day's input.
+/// Inputs that aren't UTF-8 are skipped, and parsing is allowed to fail, but not to panic.
+pub fn parse<'a, C: aoc::Parser<'a>>(data: &'a [u8]) {
+    if let Ok(input) = std::str::from_utf8(data) {
+        let _ = C::try_parse(input);
+    }
+}
+
+/// The challenge crates, as their names and directories
+pub fn days() -> io::Result<Vec<(String, PathBuf)>> {
+    let challenges = Path::new(env!("CARGO_MANIFEST_DIR")).join("../challenges");
+    let mut days = Vec::new();
+    for entry in fs::read_dir(challenges)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if name.starts_with("day") && name != "day00" {
+            days.push((name, entry.path()));
+        }
+    }
+    days.sort();
+    Ok(days)
+}
+
+/// Writes the examples from a day's README into `corpus/<day>`, along with its input if there is one,
+/// returning how many seeds were written.
+pub fn seed(name: &str, dir: &Path) -> io::Result<usize> {
+    let corpus = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("corpus")
+        .join(name);
+    fs::create_dir_all(&corpus)?;
+
+    let mut seeds = 0;
+    if let Ok(readme) = fs::read_to_string(dir.join("README.md")) {
+        for (i, example) in aoc::examples(&readme).iter().enumerate() {
+            fs::write(corpus.join(format!("readme-{i}")), example.input)?;
+            seeds += 1;
+        }
+    }
+    if let Ok(input) = fs::read(dir.join("input.txt")) {
+        fs::write(corpus.join("input"), input)?;
+        seeds += 1;
+    }
+    Ok(seeds)
+}